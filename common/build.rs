@@ -0,0 +1,38 @@
+use std::env;
+
+// Engine versions `common::profile` has a profile for.
+const VERSIONS: [&str; 1] = ["4.27"];
+
+// Engine versions without a profile yet, and what's missing.
+const MISSING: [(&str, &str); 5] = [
+    ("4.23", "it reflects properties through UProperty objects, which common doesn't model"),
+    ("4.24", "it reflects properties through UProperty objects, which common doesn't model"),
+    ("4.25", "its layouts haven't been checked against a game"),
+    ("4.26", "its layouts haven't been checked against a game"),
+    ("5.0", "its layouts haven't been checked against a game"),
+];
+
+// The engine version DRG ships with.
+const DEFAULT_VERSION: &str = "4.27";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=UE_VERSION");
+
+    let version = env::var("UE_VERSION").unwrap_or_else(|_| String::from(DEFAULT_VERSION));
+
+    if let Some((_, reason)) = MISSING.iter().find(|(missing, _)| *missing == version) {
+        panic!("UE_VERSION {} has no layout profile yet: {}", version, reason);
+    }
+
+    assert!(
+        VERSIONS.contains(&version.as_str()),
+        "unsupported UE_VERSION \"{}\". expected one of {:?}",
+        version,
+        VERSIONS
+    );
+
+    let values: Vec<String> = VERSIONS.iter().map(|v| format!("\"{}\"", v)).collect();
+    println!("cargo:rustc-check-cfg=cfg(ue, values({}))", values.join(", "));
+    println!("cargo:rustc-cfg=ue=\"{}\"", version);
+}
@@ -0,0 +1,110 @@
+use crate::list::List;
+
+use core::fmt::{self, Display, Formatter, Write};
+use core::ptr;
+use core::str;
+
+// Records what was being processed when an error happened, e.g. the full name
+// of an object or a static string like "hook Item::GetItemName".
+//
+// Errors are plain enums with no room for this, so the context lives off to
+// the side: each `.context()` on a failing `Result` or empty `Option` appends
+// one entry to a static chain as the error propagates, innermost first. Print
+// the chain with `Chain` after the error itself, and `clear` it wherever the
+// error ends up logged or handled, so the next error starts a chain of its
+// own. Lookups that callers are expected to recover from, like a property that
+// may not exist, don't add context.
+
+const MAX_CONTEXTS: usize = 16;
+const MAX_CONTEXT_LEN: usize = 256;
+
+type Entry = List<u8, MAX_CONTEXT_LEN>;
+
+static mut CONTEXTS: List<Entry, MAX_CONTEXTS> = List::new();
+static mut NUM_DROPPED: usize = 0;
+
+pub trait Context {
+    // Records `context` in the chain if `self` is an error.
+    fn context(self, context: impl Display) -> Self;
+}
+
+impl<T, E> Context for Result<T, E> {
+    fn context(self, context: impl Display) -> Self {
+        if self.is_err() {
+            unsafe {
+                push(context);
+            }
+        }
+
+        self
+    }
+}
+
+impl<T> Context for Option<T> {
+    fn context(self, context: impl Display) -> Self {
+        if self.is_none() {
+            unsafe {
+                push(context);
+            }
+        }
+
+        self
+    }
+}
+
+unsafe fn push(context: impl Display) {
+    let contexts = &mut *ptr::addr_of_mut!(CONTEXTS);
+
+    let mut entry = Entry::new();
+    let _ = write!(Truncate(&mut entry), "{}", context);
+
+    if contexts.push(entry).is_err() {
+        NUM_DROPPED += 1;
+    }
+}
+
+// Forgets the recorded chain, once its error was logged or handled.
+pub unsafe fn clear() {
+    (*ptr::addr_of_mut!(CONTEXTS)).clear();
+    NUM_DROPPED = 0;
+}
+
+// Writes as much as fits, rather than failing like `List` does when full.
+struct Truncate<'a>(&'a mut Entry);
+
+impl Write for Truncate<'_> {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        let space_left = self.0.capacity() - self.0.len();
+        let bytes = s.as_bytes();
+        let bytes = bytes.get(..space_left).unwrap_or(bytes);
+        self.0.write_bytes(bytes).map_err(|_| fmt::Error)
+    }
+}
+
+// Displays the recorded chain, one line per context, starting with the
+// context closest to where the error happened.
+pub struct Chain;
+
+impl Display for Chain {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        unsafe {
+            for entry in (*ptr::addr_of!(CONTEXTS)).iter() {
+                // Truncation can split a multibyte character.
+                let text = match str::from_utf8(entry.as_slice()) {
+                    Ok(text) => text,
+                    Err(e) => str::from_utf8_unchecked(entry.as_slice().get(..e.valid_up_to()).unwrap_or_default()),
+                };
+
+                write!(f, "\n    while processing {}", text)?;
+            }
+
+            let num_dropped = NUM_DROPPED;
+
+            if num_dropped > 0 {
+                write!(f, "\n    ({} more contexts dropped)", num_dropped)?;
+            }
+        }
+
+        Ok(())
+    }
+}
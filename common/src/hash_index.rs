@@ -0,0 +1,119 @@
+// A fixed-capacity hash table from 64-bit hashes to `u32` values, such as
+// indices into the engine's object array or name pool. Only the hash is
+// stored, not the key, so a hit is a candidate that the caller still has to
+// compare against the key it looked up.
+//
+// Like `Map`, entries can't be removed. Size `N` well above the number of
+// entries so probes stay short.
+pub struct HashIndex<const N: usize> {
+    slots: [Slot; N],
+}
+
+#[derive(Copy, Clone)]
+struct Slot {
+    hash: u32,
+    // The value plus one, so that 0 is empty.
+    value: u32,
+}
+
+impl Slot {
+    const EMPTY: Slot = Slot { hash: 0, value: 0 };
+}
+
+impl<const N: usize> HashIndex<N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [Slot::EMPTY; N],
+        }
+    }
+
+    // Returns whether there was room for the entry.
+    pub fn insert(&mut self, hash: u64, value: u32) -> bool {
+        let hash = fold(hash);
+
+        let value = match value.checked_add(1) {
+            Some(value) => value,
+            None => return false,
+        };
+
+        for probe in 0..N {
+            let index = (hash as usize).wrapping_add(probe) % N;
+
+            if let Some(slot) = self.slots.get_mut(index) {
+                if slot.value == 0 {
+                    *slot = Slot { hash, value };
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    // Yields the values of the entries whose hash may be `hash`, oldest first.
+    pub fn candidates(&self, hash: u64) -> impl Iterator<Item = u32> + '_ {
+        let hash = fold(hash);
+
+        (0..N)
+            .map(move |probe| (hash as usize).wrapping_add(probe) % N)
+            .map_while(move |index| self.slots.get(index).filter(|slot| slot.value != 0))
+            .filter(move |slot| slot.hash == hash)
+            .map(|slot| slot.value - 1)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn fold(hash: u64) -> u32 {
+    (hash ^ (hash >> 32)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_are_the_values_with_the_same_hash() {
+        let mut index = HashIndex::<16>::new();
+
+        assert!(index.insert(1, 10));
+        assert!(index.insert(2, 20));
+        assert!(index.insert(1, 11));
+
+        assert!(index.candidates(1).eq([10, 11]));
+        assert!(index.candidates(2).eq([20]));
+        assert_eq!(index.candidates(3).count(), 0);
+    }
+
+    #[test]
+    fn colliding_slots_probe_past_other_hashes() {
+        let mut index = HashIndex::<4>::new();
+
+        // 0 and 4 start probing at the same slot, as do 1 and 5.
+        assert!(index.insert(0, 0));
+        assert!(index.insert(1, 1));
+        assert!(index.insert(4, 4));
+
+        assert!(index.candidates(4).eq([4]));
+        assert!(index.candidates(0).eq([0]));
+        assert_eq!(index.candidates(5).count(), 0);
+    }
+
+    #[test]
+    fn full_index_rejects_entries() {
+        let mut index = HashIndex::<2>::new();
+
+        assert!(index.insert(7, 0));
+        assert!(index.insert(7, 1));
+        assert!(!index.insert(7, 2));
+
+        assert!(index.candidates(7).eq([0, 1]));
+    }
+
+    #[test]
+    fn largest_value_is_rejected() {
+        let mut index = HashIndex::<2>::new();
+
+        assert!(!index.insert(0, u32::MAX));
+        assert_eq!(index.candidates(0).count(), 0);
+    }
+}
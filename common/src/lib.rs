@@ -1,8 +1,8 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 #![allow(non_snake_case, non_upper_case_globals, non_camel_case_types)]
 #![allow(clippy::missing_safety_doc)]
 
-#[cfg(not(debug_assertions))]
+#[cfg(all(not(test), not(debug_assertions)))]
 #[panic_handler]
 fn panic(_: &core::panic::PanicInfo) -> ! {
     extern "Rust" {
@@ -13,7 +13,7 @@ fn panic(_: &core::panic::PanicInfo) -> ! {
     unsafe { f() }
 }
 
-#[cfg(debug_assertions)]
+#[cfg(all(not(test), debug_assertions))]
 #[panic_handler]
 fn panic(_: &core::panic::PanicInfo) -> ! {
     loop {}
@@ -25,24 +25,41 @@ use core::ops::{Deref, DerefMut};
 use core::ptr::{self, NonNull};
 use core::slice;
 
+pub mod context;
+pub use context::Context;
+
 mod fmt;
 pub use fmt::*;
 
+mod hash_index;
+
 mod name;
 pub use name::*;
 
 mod object;
 pub use object::*;
 
+pub mod profile;
+pub use profile::PROFILE;
+
 pub mod list;
 pub use list::*;
 
+pub mod map;
+pub use map::{FnvHasher, Map};
+
 mod split;
 pub use split::*;
 
+mod string;
+pub use string::*;
+
 pub mod timer;
 pub use timer::Timer;
 
+mod tmap;
+pub use tmap::*;
+
 mod util;
 
 pub mod win;
@@ -51,6 +68,7 @@ pub mod win;
 pub enum Error {
     FindNamePoolData,
     Object(#[from] object::Error),
+    String(#[from] string::Error),
 }
 
 #[derive(Copy, Clone)]
@@ -87,22 +105,10 @@ impl<T> DerefMut for TArray<T> {
     }
 }
 
-#[derive(Copy, Clone)]
 #[repr(C)]
-pub struct FString {
-    data: *const u16,
-    pub len: i32,
-    pub capacity: i32,
-}
-
-impl<'a> From<&'a [u16]> for FString {
-    fn from(s: &[u16]) -> FString {
-        FString {
-            data: s.as_ptr(),
-            len: s.len() as i32,
-            capacity: s.len() as i32,
-        }
-    }
+pub struct TPair<K, V> {
+    pub Key: K,
+    pub Value: V,
 }
 
 #[repr(C)]
@@ -129,6 +135,34 @@ pub struct FWeakObjectPtr {
 }
 
 impl FWeakObjectPtr {
+    pub const fn null() -> FWeakObjectPtr {
+        FWeakObjectPtr {
+            ObjectIndex: -1,
+            ObjectSerialNumber: 0,
+        }
+    }
+
+    // Gives the object a serial number if it doesn't have one yet, the way
+    // the engine does when it first makes a weak pointer to an object. If
+    // `GUObjectArray`'s counter couldn't be checked, an object without a
+    // number keeps none, and the pointer never resolves.
+    pub unsafe fn new(object: *const UObject) -> FWeakObjectPtr {
+        if object.is_null() {
+            return FWeakObjectPtr::null();
+        }
+
+        let item = (*object).item();
+
+        if item.is_null() {
+            FWeakObjectPtr::null()
+        } else {
+            FWeakObjectPtr {
+                ObjectIndex: (*object).InternalIndex,
+                ObjectSerialNumber: (*GUObjectArray).allocate_serial_number(item),
+            }
+        }
+    }
+
     pub unsafe fn get(&self) -> *mut UObject {
         if self.ObjectSerialNumber == 0 || self.ObjectIndex < 0 {
             ptr::null_mut()
@@ -151,11 +185,101 @@ pub struct TWeakObjectPtr<T> {
 }
 
 impl<T> TWeakObjectPtr<T> {
+    pub const fn null() -> TWeakObjectPtr<T> {
+        TWeakObjectPtr {
+            base: FWeakObjectPtr::null(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub unsafe fn new(object: *const T) -> TWeakObjectPtr<T> {
+        TWeakObjectPtr {
+            base: FWeakObjectPtr::new(object.cast()),
+            _marker: PhantomData,
+        }
+    }
+
     pub unsafe fn get(&self) -> *mut T {
         self.base.get().cast()
     }
 }
 
+// An object pointer that is safe to keep across frames, e.g. in a `static mut`.
+// Every access checks that the object is still alive, so once the garbage
+// collector destroys it, `get()` returns `None` rather than a dangling pointer.
+//
+// Like `FWeakObjectPtr`, this checks the serial number of the object's slot
+// in `GUObjectArray`, and on top of that that the slot still holds the same
+// address.
+pub struct Handle<T> {
+    object: *mut T,
+    index: i32,
+    serial_number: i32,
+}
+
+impl<T> Handle<T> {
+    pub const fn null() -> Handle<T> {
+        Handle {
+            object: ptr::null_mut(),
+            index: -1,
+            serial_number: 0,
+        }
+    }
+
+    // `object` must be a `UObject`, such as a class struct from the SDK.
+    pub unsafe fn new(object: *mut T) -> Handle<T> {
+        let weak = FWeakObjectPtr::new(object.cast());
+
+        if object.is_null() || weak.ObjectIndex < 0 {
+            Handle::null()
+        } else {
+            Handle {
+                object,
+                index: weak.ObjectIndex,
+                serial_number: weak.ObjectSerialNumber,
+            }
+        }
+    }
+
+    pub unsafe fn is_valid(&self) -> bool {
+        if self.object.is_null() {
+            return false;
+        }
+
+        let item = (*GUObjectArray).index_to_object(self.index);
+
+        !item.is_null()
+            && ptr::eq((*item).Object, self.object.cast())
+            && (self.serial_number == 0 || (*item).SerialNumber == self.serial_number)
+            && (*item).is_valid()
+    }
+
+    pub unsafe fn get(&mut self) -> Option<&mut T> {
+        if self.is_valid() {
+            self.object.as_mut()
+        } else {
+            None
+        }
+    }
+
+    // The object, or null once it's gone.
+    pub unsafe fn ptr(self) -> *mut T {
+        if self.is_valid() {
+            self.object
+        } else {
+            ptr::null_mut()
+        }
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
 #[repr(C)]
 pub struct FScriptDelegate {
     Object: FWeakObjectPtr,
@@ -1,7 +1,7 @@
 use core::fmt::{self, Write};
 use core::mem::MaybeUninit;
 use core::ptr;
-use core::slice::{self, Iter};
+use core::slice::{self, Iter, IterMut};
 use core::str;
 
 #[derive(macros::NoPanicErrorDebug)]
@@ -46,10 +46,14 @@ impl<T, const N: usize> List<T, N> {
         }
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         self.as_slice().iter()
     }
 
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
     pub fn push(&mut self, value: T) -> Result<(), Error> {
         if self.len < self.capacity() {
             // Safe to use direct assignment since dropping a MaybeUninit<T> is a no-op.
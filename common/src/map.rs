@@ -0,0 +1,217 @@
+use core::hash::{Hash, Hasher};
+
+#[derive(macros::NoPanicErrorDebug)]
+pub enum Error {
+    CapacityReached,
+}
+
+// 64-bit FNV-1a.
+pub struct FnvHasher(u64);
+
+impl FnvHasher {
+    pub const fn new() -> FnvHasher {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+enum Probe {
+    Occupied(usize),
+    Vacant(usize),
+    Full,
+}
+
+// A fixed-capacity hash map with open addressing and linear probing. Entries
+// can't be removed, which keeps lookups free of tombstones. Keep the load
+// factor low by sizing `N` well above the number of entries.
+pub struct Map<K, V, const N: usize> {
+    slots: [Option<(K, V)>; N],
+    len: usize,
+}
+
+impl<K, V, const N: usize> Map<K, V, N> {
+    const EMPTY_SLOT: Option<(K, V)> = None;
+
+    pub const fn new() -> Self {
+        Self {
+            slots: [Self::EMPTY_SLOT; N],
+            len: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots
+            .iter()
+            .filter_map(|slot| slot.as_ref().map(|(key, value)| (key, value)))
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize> Map<K, V, N> {
+    fn probe(&self, key: &K) -> Probe {
+        let mut hasher = FnvHasher::new();
+        key.hash(&mut hasher);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let start = hasher.finish() as usize;
+
+        for i in 0..N {
+            let index = start.wrapping_add(i) % N;
+
+            // SAFETY: `index` is reduced modulo the slot count.
+            match unsafe { self.slots.get_unchecked(index) } {
+                Some((k, _)) if k == key => return Probe::Occupied(index),
+                Some(_) => {}
+                None => return Probe::Vacant(index),
+            }
+        }
+
+        Probe::Full
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        if let Probe::Occupied(index) = self.probe(key) {
+            unsafe { self.slots.get_unchecked(index).as_ref().map(|(_, v)| v) }
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        if let Probe::Occupied(index) = self.probe(key) {
+            unsafe { self.slots.get_unchecked_mut(index).as_mut().map(|(_, v)| v) }
+        } else {
+            None
+        }
+    }
+
+    // Returns the value that `key` previously mapped to, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, Error> {
+        match self.probe(&key) {
+            Probe::Occupied(index) => {
+                let slot = unsafe { self.slots.get_unchecked_mut(index) };
+                Ok(slot.replace((key, value)).map(|(_, v)| v))
+            }
+
+            Probe::Vacant(index) => {
+                unsafe {
+                    *self.slots.get_unchecked_mut(index) = Some((key, value));
+                }
+
+                self.len += 1;
+                Ok(None)
+            }
+
+            Probe::Full => Err(Error::CapacityReached),
+        }
+    }
+}
+
+impl<K, V, const N: usize> Default for Map<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hashes every key to the same slot, so each insert has to probe past the
+    // ones before it.
+    #[derive(PartialEq, Eq)]
+    struct Colliding(u32);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            0_u32.hash(state);
+        }
+    }
+
+    #[test]
+    fn insert_and_get() {
+        let mut map = Map::<u32, &str, 8>::new();
+
+        assert!(matches!(map.insert(1, "one"), Ok(None)));
+        assert!(matches!(map.insert(2, "two"), Ok(None)));
+
+        assert_eq!(map.get(&1), Some(&"one"));
+        assert_eq!(map.get(&2), Some(&"two"));
+        assert_eq!(map.get(&3), None);
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn insert_replaces_existing_value() {
+        let mut map = Map::<u32, u32, 8>::new();
+
+        assert!(matches!(map.insert(1, 10), Ok(None)));
+        assert!(matches!(map.insert(1, 11), Ok(Some(10))));
+
+        assert_eq!(map.get(&1), Some(&11));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn colliding_keys_probe_to_later_slots() {
+        let mut map = Map::<Colliding, u32, 4>::new();
+
+        for i in 0..4 {
+            assert!(matches!(map.insert(Colliding(i), i), Ok(None)));
+        }
+
+        for i in 0..4 {
+            assert_eq!(map.get(&Colliding(i)), Some(&i));
+        }
+
+        if let Some(value) = map.get_mut(&Colliding(3)) {
+            *value = 30;
+        }
+
+        assert_eq!(map.get(&Colliding(3)), Some(&30));
+    }
+
+    #[test]
+    fn full_map_rejects_new_keys_only() {
+        let mut map = Map::<Colliding, u32, 2>::new();
+
+        assert!(map.insert(Colliding(0), 0).is_ok());
+        assert!(map.insert(Colliding(1), 1).is_ok());
+
+        assert!(matches!(map.insert(Colliding(2), 2), Err(Error::CapacityReached)));
+        assert!(matches!(map.insert(Colliding(1), 10), Ok(Some(1))));
+        assert_eq!(map.get(&Colliding(2)), None);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::util;
 use crate::win;
 use crate::Error;
+use crate::PROFILE;
 
 use core::cmp::Ordering;
 use core::ffi::c_void;
@@ -9,16 +10,21 @@ use core::mem;
 use core::ptr;
 use core::str;
 
+mod index;
+use index::IndexGuard;
+
 pub static mut NamePoolData: *const FNamePool = ptr::null();
 
-const FNameMaxBlockBits: u8 = 13;
-const FNameBlockOffsetBits: u8 = 16;
+const FNameMaxBlockBits: u8 = PROFILE.fname_max_block_bits;
+const FNameBlockOffsetBits: u8 = PROFILE.fname_block_offset_bits;
 const FNameMaxBlocks: usize = 1 << FNameMaxBlockBits;
 const FNameBlockOffsets: usize = 1 << FNameBlockOffsetBits;
 const Stride: usize = mem::align_of::<FNameEntry>();
 const BlockSizeBytes: usize = Stride * FNameBlockOffsets;
 
-#[derive(Copy, Clone)]
+// Names compare and hash by entry and number, like the engine's, rather than
+// by text.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(C)]
 pub struct FName {
     ComparisonIndex: FNameEntryId,
@@ -26,6 +32,19 @@ pub struct FName {
 }
 
 impl FName {
+    // Looks up the existing name that `text` refers to, without adding it to
+    // the name pool. Like the engine, a trailing "_N" is split off as the
+    // number, and the rest compares case-insensitively.
+    pub unsafe fn find(text: &str) -> Option<FName> {
+        let (text, number) = split_number(text.as_bytes());
+        let id = IndexGuard::lock().find(&*NamePoolData, text)?;
+
+        Some(FName {
+            ComparisonIndex: id,
+            Number: number,
+        })
+    }
+
     unsafe fn entry(&self) -> *const FNameEntry {
         self.ComparisonIndex.entry()
     }
@@ -37,6 +56,60 @@ impl FName {
     pub fn number(&self) -> u32 {
         self.Number
     }
+
+    // The engine's `GetTypeHash(FName)`, which places names in the buckets of
+    // engine maps.
+    pub fn type_hash(&self) -> u32 {
+        self.ComparisonIndex.Value.wrapping_add(self.Number)
+    }
+}
+
+// A name to look something up by: either an `FName`, or text to find one from.
+pub trait ToFName {
+    unsafe fn to_fname(&self) -> Option<FName>;
+}
+
+impl ToFName for FName {
+    unsafe fn to_fname(&self) -> Option<FName> {
+        Some(*self)
+    }
+}
+
+impl ToFName for &str {
+    unsafe fn to_fname(&self) -> Option<FName> {
+        FName::find(self)
+    }
+}
+
+// Splits "Rock_3" into "Rock" and the internal number 4, the same way the
+// engine's `ParseNumber` does when it makes an `FName` from a string. Numbers
+// with leading zeros, like "Rock_03", and numbers that don't fit an int32 stay
+// part of the text.
+pub(crate) fn split_number(text: &[u8]) -> (&[u8], u32) {
+    // The digits of the largest int32.
+    const MAX_DIGITS: usize = 10;
+
+    let num_digits = text.iter().rev().take_while(|c| c.is_ascii_digit()).count();
+    let split = text.len() - num_digits;
+
+    let (base, digits) = match (text.get(..split), text.get(split..)) {
+        (Some(base), Some(digits)) => (base, digits),
+        _ => return (text, 0),
+    };
+
+    let is_number = (1..=MAX_DIGITS).contains(&num_digits)
+        && !(num_digits > 1 && digits.first() == Some(&b'0'))
+        && base.last() == Some(&b'_');
+
+    let number = digits
+        .iter()
+        .fold(0, |number, digit| 10 * number + u64::from(digit - b'0'));
+
+    if !is_number || number >= i32::MAX as u64 {
+        return (text, 0);
+    }
+
+    (base.get(..base.len() - 1).unwrap_or(base), number as u32 + 1)
 }
 
 impl Display for FName {
@@ -51,8 +124,29 @@ impl Display for FName {
     }
 }
 
+// How script bytecode stores a name. The display index only differs from the
+// comparison index in builds with case-preserving names.
 #[derive(Copy, Clone)]
 #[repr(C)]
+pub struct FScriptName {
+    pub ComparisonIndex: u32,
+    pub DisplayIndex: u32,
+    pub Number: u32,
+}
+
+impl FScriptName {
+    pub fn to_fname(&self) -> FName {
+        FName {
+            ComparisonIndex: FNameEntryId {
+                Value: self.ComparisonIndex,
+            },
+            Number: self.Number,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(C)]
 pub struct FNameEntryId {
     Value: u32,
 }
@@ -143,19 +237,26 @@ impl FNamePool {
         Ok(())
     }
 
-    pub unsafe fn iter(&self) -> NameIterator {
-        let first_block_size = if self.CurrentBlock > 0 {
+    pub unsafe fn iter(&self) -> NameIterator<'_> {
+        self.iter_from(0, 0)
+    }
+
+    // Iterates the entries from `byte_cursor` within `block` onward.
+    unsafe fn iter_from(&self, block: u32, byte_cursor: usize) -> NameIterator<'_> {
+        let block_size = if self.CurrentBlock > block {
             BlockSizeBytes
         } else {
             self.CurrentByteCursor as usize
         };
 
+        let block_start = *self.Blocks.get_unchecked(block as usize);
+
         NameIterator {
             pool: self,
-            block: 0,
-            block_start: self.Blocks[0],
-            cursor_within_block: self.Blocks[0],
-            block_end: self.Blocks[0].add(first_block_size - mem::size_of::<FNameEntryHeader>()),
+            block,
+            block_start,
+            cursor_within_block: block_start.add(byte_cursor),
+            block_end: block_start.add(block_size.saturating_sub(mem::size_of::<FNameEntryHeader>())),
         }
     }
 }
@@ -283,3 +384,48 @@ impl Display for FNameEntry {
         unsafe { f.write_str(self.text()) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::split_number;
+
+    fn split(text: &str) -> (&str, u32) {
+        let (base, number) = split_number(text.as_bytes());
+        (core::str::from_utf8(base).unwrap(), number)
+    }
+
+    #[test]
+    fn trailing_number_is_split_off() {
+        assert_eq!(split("Rock_3"), ("Rock", 4));
+        assert_eq!(split("Rock_1_2"), ("Rock_1", 3));
+    }
+
+    #[test]
+    fn zero_is_a_number() {
+        assert_eq!(split("Rock_0"), ("Rock", 1));
+        assert_eq!(split("_0"), ("", 1));
+    }
+
+    #[test]
+    fn leading_zeros_stay_in_the_text() {
+        assert_eq!(split("Rock_01"), ("Rock_01", 0));
+        assert_eq!(split("Rock_00"), ("Rock_00", 0));
+    }
+
+    #[test]
+    fn text_without_a_number_is_unchanged() {
+        assert_eq!(split("Rock"), ("Rock", 0));
+        assert_eq!(split("Rock_"), ("Rock_", 0));
+        assert_eq!(split("Rock3"), ("Rock3", 0));
+        assert_eq!(split("123"), ("123", 0));
+        assert_eq!(split(""), ("", 0));
+    }
+
+    #[test]
+    fn numbers_must_fit_an_int32() {
+        assert_eq!(split("Rock_2147483646"), ("Rock", 2147483647));
+        assert_eq!(split("Rock_2147483647"), ("Rock_2147483647", 0));
+        assert_eq!(split("Rock_9999999999"), ("Rock_9999999999", 0));
+        assert_eq!(split("Rock_12345678901"), ("Rock_12345678901", 0));
+    }
+}
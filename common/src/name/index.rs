@@ -0,0 +1,131 @@
+use super::{FNameEntryId, FNamePool};
+use crate::hash_index::HashIndex;
+use crate::FnvHasher;
+
+use core::hash::Hasher;
+use core::hint;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Slots in the table. Keep it well above the number of names in the pool so
+// probes stay short. Each slot is 8 bytes.
+const CAPACITY: usize = 1 << 20;
+
+static mut INDEX: NameIndex = NameIndex::new();
+
+// Whether someone holds an `IndexGuard`.
+static IS_LOCKED: AtomicBool = AtomicBool::new(false);
+
+// Exclusive access to the shared index until dropped. Like the object index,
+// it's used from whichever thread calls `FName::find`, including the game
+// thread inside a hook.
+pub struct IndexGuard(());
+
+impl IndexGuard {
+    pub fn lock() -> IndexGuard {
+        while IS_LOCKED
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+
+        IndexGuard(())
+    }
+}
+
+impl Deref for IndexGuard {
+    type Target = NameIndex;
+
+    fn deref(&self) -> &NameIndex {
+        unsafe { &*ptr::addr_of!(INDEX) }
+    }
+}
+
+impl DerefMut for IndexGuard {
+    fn deref_mut(&mut self) -> &mut NameIndex {
+        unsafe { &mut *ptr::addr_of_mut!(INDEX) }
+    }
+}
+
+impl Drop for IndexGuard {
+    fn drop(&mut self) {
+        IS_LOCKED.store(false, Ordering::Release);
+    }
+}
+
+// A hash table from a name's text to its entry in the name pool, so a `&str`
+// can be turned into an `FName` without comparing it against every entry.
+//
+// Like the engine's own name hash, it ignores ASCII case. Entries are only
+// ever appended to the pool, so the index picks up where it left off.
+pub struct NameIndex {
+    table: HashIndex<CAPACITY>,
+    indexed_block: u32,
+    indexed_byte_cursor: u32,
+    is_full: bool,
+}
+
+impl NameIndex {
+    pub const fn new() -> NameIndex {
+        NameIndex {
+            table: HashIndex::new(),
+            indexed_block: 0,
+            indexed_byte_cursor: 0,
+            is_full: false,
+        }
+    }
+
+    // Indexes the entries added to `pool` since the last update.
+    unsafe fn update(&mut self, pool: &FNamePool) {
+        let end = (pool.CurrentBlock, pool.CurrentByteCursor);
+
+        if self.is_full || end == (self.indexed_block, self.indexed_byte_cursor) {
+            return;
+        }
+
+        for (id, entry) in pool.iter_from(self.indexed_block, self.indexed_byte_cursor as usize) {
+            if (*entry).Header.is_wide() {
+                // `&str`s can't name these.
+                continue;
+            }
+
+            if !self.table.insert(hash((*entry).text().as_bytes()), id.value()) {
+                self.is_full = true;
+                return;
+            }
+        }
+
+        (self.indexed_block, self.indexed_byte_cursor) = end;
+    }
+
+    pub unsafe fn find(&mut self, pool: &FNamePool, text: &[u8]) -> Option<FNameEntryId> {
+        self.update(pool);
+
+        let is_match = |id: FNameEntryId| (*id.entry()).text().as_bytes().eq_ignore_ascii_case(text);
+
+        let found = self
+            .table
+            .candidates(hash(text))
+            .map(|value| FNameEntryId { Value: value })
+            .find(|id| is_match(*id));
+
+        if found.is_none() && self.is_full {
+            // Names that didn't fit are only reachable by a scan.
+            pool.iter().map(|(id, _)| id).find(|id| is_match(*id))
+        } else {
+            found
+        }
+    }
+}
+
+fn hash(text: &[u8]) -> u64 {
+    let mut hasher = FnvHasher::new();
+
+    for byte in text {
+        hasher.write_u8(byte.to_ascii_lowercase());
+    }
+
+    hasher.finish()
+}
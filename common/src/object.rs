@@ -1,7 +1,10 @@
 use crate::split::ReverseSplitIterator;
 use crate::win;
 use crate::FName;
-use crate::List;
+use crate::ToFName;
+use crate::FString;
+use crate::PROFILE;
+use crate::{TArray, TMap, TPair};
 
 use core::convert::TryFrom;
 use core::ffi::c_void;
@@ -10,17 +13,50 @@ use core::mem;
 use core::ops::BitOr;
 use core::ptr;
 use core::str;
+use core::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+
+mod call;
+pub use call::{Call, ParmsArena, ParmsBuffer};
+
+mod frame;
+pub use frame::{Arguments, FOutParmRec, GNatives, HookParameters, OutArgument};
 
 mod full_name;
 use full_name::FullName;
 
+mod function_hook;
+pub use function_hook::UFunctionHook;
+
+mod flags;
+pub use flags::{EInternalObjectFlags, EObjectFlags};
+
+mod index;
+use index::IndexGuard;
+
+mod parameter;
+pub use parameter::{Parameter, ParameterDirection, Parameters};
+
+mod path_name;
+pub use path_name::{FullNameOf, PackageNameOf, PathNameOf};
+
+mod property;
+pub use property::*;
+
+mod script;
+pub use script::{Disassembly, EExprToken, LiveReferences, RawReferences, ScriptReferences};
+
 pub static mut GUObjectArray: *const FUObjectArray = ptr::null();
 
-const NumElementsPerChunk: usize = 64 * 1024;
+// Whether `FUObjectArray::MasterSerialNumber` looked like the engine's counter
+// at init. Its offset comes from the engine source, and incrementing the wrong
+// field would corrupt `GUObjectArray`, so nothing writes to it otherwise.
+static HAS_MASTER_SERIAL_NUMBER: AtomicBool = AtomicBool::new(false);
+
+const NumElementsPerChunk: usize = PROFILE.num_elements_per_chunk;
 
 // The maximum number of outers we can store in an array.
 // Set to a large enough number to cover the outers length of all objects.
-// Used when constructing an object's name, as well as for name comparisons.
+// Used for name comparisons.
 const MAX_OUTERS: usize = 32;
 
 #[derive(macros::NoPanicErrorDebug)]
@@ -29,6 +65,13 @@ pub enum Error {
     Fmt(#[from] fmt::Error),
     FullName(#[from] full_name::Error),
     UnableToFind(&'static str),
+    PropertyNotFound,
+    PropertyTypeMismatch,
+    FunctionNotFound,
+    ParameterNotFound,
+    ParmsTooLarge(usize),
+    NativesNotFound,
+    ArgumentNotAddressable,
 }
 
 #[repr(C)]
@@ -38,6 +81,8 @@ pub struct FUObjectArray {
     MaxObjectsNotConsideredByGC: i32,
     OpenForDisregardForGC: bool,
     pub ObjObjects: TUObjectArray,
+    pad0: [u8; PROFILE.fuobjectarray_size_before_master_serial_number],
+    MasterSerialNumber: AtomicI32,
 }
 
 impl FUObjectArray {
@@ -60,80 +105,99 @@ impl FUObjectArray {
 
         GUObjectArray = instruction_after_mov.add(mov_immediate as usize).sub(0x10).cast();
 
-        Ok(())
-    }
+        HAS_MASTER_SERIAL_NUMBER.store((*GUObjectArray).is_master_serial_number_plausible(), Ordering::Relaxed);
 
-    #[inline(never)]
-    pub unsafe fn find_function(&self, name: &'static str) -> *mut UFunction {
-        self.find(name)
-            .map(|f| f.cast())
-            .unwrap_or(core::ptr::null_mut())
+        Ok(())
     }
 
-    pub unsafe fn find(&self, name: &'static str) -> Result<*mut UObject, Error> {
-        // Do a short-circuiting name comparison.
+    // The engine numbers objects upwards from START_SERIAL_NUMBER, one at a
+    // time, so its counter is past that by at least as many objects as have a
+    // number, and no object has a number past the counter.
+    unsafe fn is_master_serial_number_plausible(&self) -> bool {
+        const START_SERIAL_NUMBER: i32 = 1000;
 
-        // Compare the class from `name` against the class in `self`.
-        // Then compare the outers in `name` against the outers in `self`.
+        let master = self.MasterSerialNumber.load(Ordering::Relaxed);
+        let mut num_numbered: i32 = 0;
 
-        // This way, we don't have to construct the full name of `self` if we
-        // can rule out non-matching classes and outers sooner.
+        if master < START_SERIAL_NUMBER {
+            return false;
+        }
 
-        let target = FullName::<MAX_OUTERS>::try_from(name)?;
+        for index in 0..self.ObjObjects.NumElements {
+            let item = self.index_to_object(index);
 
-        'outer: for object in self.iter() {
-            if object.is_null() {
-                // We're not looking for a null object.
+            if item.is_null() || (*item).SerialNumber == 0 {
                 continue;
             }
 
-            let my_name = (*object).name().as_bytes();
-
-            if my_name != target.name {
-                // Object names don't match.
-                // No need to check the class. Let's bail.
-                continue;
+            if (*item).SerialNumber <= START_SERIAL_NUMBER || (*item).SerialNumber > master {
+                return false;
             }
 
-            let my_class = (*(*object).ClassPrivate).name().as_bytes();
+            num_numbered += 1;
+        }
 
-            if my_class != target.class {
-                // Classes don't match.
-                // No need to check the outers. Let's bail.
-                continue;
-            }
+        master - START_SERIAL_NUMBER >= num_numbered
+    }
 
-            let mut my_outer = (*object).OuterPrivate;
+    #[inline(never)]
+    pub unsafe fn find_function(&self, name: &'static str) -> *mut UFunction {
+        self.find(name)
+            .map(|f| f.cast())
+            .unwrap_or(core::ptr::null_mut())
+    }
 
-            for target_outer in target.outers.iter() {
-                if my_outer.is_null() {
-                    // We have no more outers left to check for this object, but
-                    // we still have target outers. So this object can't be what
-                    // we're looking for. Let's check out the next object.
-                    continue 'outer;
-                }
+    pub unsafe fn find(&self, name: &'static str) -> Result<*mut UObject, Error> {
+        let target = FullName::<MAX_OUTERS>::try_from(name)?;
+        let hash = index::hash_full_name(&target);
 
-                let my_outer_name = (*my_outer).name().as_bytes();
+        let mut object_index = IndexGuard::lock();
+        object_index.update(self);
 
-                if my_outer_name != *target_outer {
-                    // This outer doesn't match the target outer we're looking for.
-                    // No need to check the remaining outers. Let's bail.
-                    continue 'outer;
-                }
+        for candidate in object_index.candidates(hash) {
+            let item = self.index_to_object(candidate as i32);
 
-                // Advance up to the next outer.
-                my_outer = (*my_outer).OuterPrivate;
+            if !item.is_null() && is_named((*item).Object, &target) {
+                return Ok((*item).Object);
             }
+        }
 
-            // We got here because the name, class, and outers all match the
-            // input name. So our search is over.
-            return Ok(object);
+        // The index misses objects that were created in slots freed before it
+        // last grew, or that didn't fit. Fall back to a scan, and index what
+        // it finds for next time.
+        for (i, object) in self.iter().enumerate() {
+            if is_named(object, &target) {
+                object_index.insert(hash, i);
+                return Ok(object);
+            }
         }
 
         // No object matched our search.
         Err(Error::UnableToFind(name))
     }
 
+    // FUObjectArray::AllocateSerialNumber. The engine only numbers an object
+    // once something makes a weak pointer to it. If two threads number the
+    // same object at once, both get whichever number was stored first.
+    //
+    // Returns 0 for an object without a number if the counter failed its check
+    // at init.
+    pub unsafe fn allocate_serial_number(&self, item: *const FUObjectItem) -> i32 {
+        let serial_number = &*ptr::addr_of!((*item).SerialNumber).cast::<AtomicI32>();
+        let current = serial_number.load(Ordering::Acquire);
+
+        if current != 0 || !HAS_MASTER_SERIAL_NUMBER.load(Ordering::Relaxed) {
+            return current;
+        }
+
+        let new = self.MasterSerialNumber.fetch_add(1, Ordering::SeqCst).wrapping_add(1);
+
+        match serial_number.compare_exchange(0, new, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => new,
+            Err(current) => current,
+        }
+    }
+
     pub unsafe fn index_to_object(&self, index: i32) -> *const FUObjectItem {
         if index < self.ObjObjects.NumElements {
             let index = index as usize;
@@ -151,6 +215,78 @@ impl FUObjectArray {
             index: 0,
         }
     }
+
+    pub fn iter_instances_of(&self, class: *const UClass) -> InstanceIterator {
+        InstanceIterator {
+            objects: self.iter(),
+            class,
+        }
+    }
+
+    pub unsafe fn iter_instances<T: StaticClass>(&self) -> Result<impl Iterator<Item = *mut T>, Error> {
+        let class = self.find(T::FULL_NAME)?;
+        Ok(self.iter_instances_of(class.cast()).map(|object| object.cast()))
+    }
+}
+
+// A Rust type that mirrors an engine class, such as a class struct in the
+// generated SDK.
+pub trait StaticClass {
+    // The class's full name, e.g. "Class /Script/FSD.PlayerCharacter".
+    const FULL_NAME: &'static str;
+}
+
+// Whether `object` has the name, class and outers in `target`.
+unsafe fn is_named<const NUM_OUTERS: usize>(object: *const UObject, target: &FullName<NUM_OUTERS>) -> bool {
+    // Do a short-circuiting name comparison.
+
+    // Compare the class from `target` against the class of `object`.
+    // Then compare the outers in `target` against the outers of `object`.
+
+    // This way, we don't have to construct the full name of `object` if we
+    // can rule out non-matching classes and outers sooner.
+
+    if object.is_null() {
+        // We're not looking for a null object.
+        return false;
+    }
+
+    if !target.name.matches((*object).NamePrivate) {
+        // Object names don't match.
+        // No need to check the class. Let's bail.
+        return false;
+    }
+
+    let my_class = (*(*object).ClassPrivate).name().as_bytes();
+
+    if my_class != target.class {
+        // Classes don't match.
+        // No need to check the outers. Let's bail.
+        return false;
+    }
+
+    let mut my_outer = (*object).OuterPrivate;
+
+    for target_outer in target.outers.iter() {
+        if my_outer.is_null() {
+            // We have no more outers left to check for this object, but
+            // we still have target outers. So this object can't be what
+            // we're looking for.
+            return false;
+        }
+
+        if !target_outer.matches((*my_outer).NamePrivate) {
+            // This outer doesn't match the target outer we're looking for.
+            // No need to check the remaining outers. Let's bail.
+            return false;
+        }
+
+        // Advance up to the next outer.
+        my_outer = (*my_outer).OuterPrivate;
+    }
+
+    // We got here because the name, class, and outers all match.
+    true
 }
 
 pub struct ObjectIterator {
@@ -159,17 +295,14 @@ pub struct ObjectIterator {
     index: usize,
 }
 
-impl Iterator for ObjectIterator {
-    type Item = *mut UObject;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl ObjectIterator {
+    fn next_item(&mut self) -> Option<*const FUObjectItem> {
         unsafe {
             if self.index < self.num_objects {
                 let chunk = *self.chunks.add(self.index / NumElementsPerChunk);
-                let object = chunk.add(self.index % NumElementsPerChunk);
-                let object = (*object).Object;
+                let item = chunk.add(self.index % NumElementsPerChunk);
                 self.index += 1;
-                Some(object)
+                Some(item)
             } else {
                 None
             }
@@ -177,6 +310,42 @@ impl Iterator for ObjectIterator {
     }
 }
 
+impl Iterator for ObjectIterator {
+    type Item = *mut UObject;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_item().map(|item| unsafe { (*item).Object })
+    }
+}
+
+// Iterates the live instances of a class and its subclasses: objects that
+// aren't null, unreachable, pending kill, class default objects or archetypes.
+pub struct InstanceIterator {
+    objects: ObjectIterator,
+    class: *const UClass,
+}
+
+impl Iterator for InstanceIterator {
+    type Item = *mut UObject;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            loop {
+                let item = self.objects.next_item()?;
+                let object = (*item).Object;
+
+                if !object.is_null()
+                    && (*item).is_valid()
+                    && !(*object).is_template()
+                    && (*object).is(self.class)
+                {
+                    return Some(object);
+                }
+            }
+        }
+    }
+}
+
 #[repr(C)]
 pub struct TUObjectArray {
     Objects: *const *mut FUObjectItem,
@@ -190,20 +359,18 @@ pub struct TUObjectArray {
 #[repr(C)]
 pub struct FUObjectItem {
     pub Object: *mut UObject,
-    Flags: i32,
+    pub Flags: EInternalObjectFlags,
     ClusterRootIndex: i32,
     pub SerialNumber: i32,
 }
 
 impl FUObjectItem {
     pub fn is_unreachable(&self) -> bool {
-        const UNREACHABLE: i32 = 1 << 28;
-        self.Flags & UNREACHABLE == UNREACHABLE
+        self.Flags.contains(EInternalObjectFlags::Unreachable)
     }
 
     pub fn is_pending_kill(&self) -> bool {
-        const PENDING_KILL: i32 = 1 << 29;
-        self.Flags & PENDING_KILL == PENDING_KILL
+        self.Flags.contains(EInternalObjectFlags::PendingKill)
     }
 
     pub fn is_valid(&self) -> bool {
@@ -240,7 +407,7 @@ macro_rules! impl_deref {
 #[repr(C)]
 pub struct UObject {
     pub vtable: *mut *const c_void,
-    ObjectFlags: u32, //EObjectFlags
+    pub ObjectFlags: EObjectFlags,
     pub InternalIndex: i32,
     ClassPrivate: *const UClass,
     pub NamePrivate: FName,
@@ -280,6 +447,90 @@ impl UObject {
         self.NamePrivate.text()
     }
 
+    // The delimiter that follows this object's name in the path of an object it
+    // contains. Like the engine, this is ':' for an object that isn't a package
+    // but sits directly in one, such as a class, and '.' otherwise.
+    unsafe fn subobject_delimiter(&self) -> char {
+        let is_package = self.fast_is(EClassCastFlags::CASTCLASS_UPackage);
+        let outer = self.OuterPrivate;
+
+        if !is_package && !outer.is_null() && (*outer).fast_is(EClassCastFlags::CASTCLASS_UPackage) {
+            ':'
+        } else {
+            '.'
+        }
+    }
+
+    // This object's slot in `GUObjectArray`, which holds its internal flags.
+    pub unsafe fn item(&self) -> *const FUObjectItem {
+        (*GUObjectArray).index_to_object(self.InternalIndex)
+    }
+
+    unsafe fn has_internal_flags(&self, flags: EInternalObjectFlags) -> bool {
+        let item = self.item();
+        !item.is_null() && (*item).Flags.contains(flags)
+    }
+
+    pub fn is_default_object(&self) -> bool {
+        self.ObjectFlags.contains(EObjectFlags::RF_ClassDefaultObject)
+    }
+
+    pub fn is_archetype(&self) -> bool {
+        self.ObjectFlags.contains(EObjectFlags::RF_ArchetypeObject)
+    }
+
+    // Whether this object is a class default object or an archetype, i.e. a
+    // template that instances are created from rather than an instance.
+    pub fn is_template(&self) -> bool {
+        self.is_default_object() || self.is_archetype()
+    }
+
+    // Whether this object is never saved to disk.
+    pub fn is_transient(&self) -> bool {
+        self.ObjectFlags.contains(EObjectFlags::RF_Transient)
+    }
+
+    // Whether the garbage collector keeps this object alive regardless of
+    // references to it.
+    pub unsafe fn is_rooted(&self) -> bool {
+        self.has_internal_flags(EInternalObjectFlags::RootSet)
+    }
+
+    // Whether this object was created by native code, such as a class compiled
+    // into the game, rather than loaded or spawned.
+    pub unsafe fn is_native(&self) -> bool {
+        self.has_internal_flags(EInternalObjectFlags::Native)
+    }
+
+    // Reads the property called `name` that this object's class, or one of its
+    // super classes, declares. Fails if `T` isn't the property's type.
+    pub unsafe fn get<T: PropertyValue>(&self, name: &str) -> Result<T, Error> {
+        let property = self.typed_property::<T>(name)?;
+        Ok(T::read((self as *const Self).cast(), property))
+    }
+
+    // Writes the property called `name`, checked the same way as `get`.
+    pub unsafe fn set<T: PropertyValue>(&mut self, name: &str, value: T) -> Result<(), Error> {
+        let property = self.typed_property::<T>(name)?;
+
+        if !value.accepts(property) {
+            return Err(Error::PropertyTypeMismatch);
+        }
+
+        value.write((self as *mut Self).cast(), property);
+        Ok(())
+    }
+
+    unsafe fn typed_property<'a, T: PropertyValue>(&self, name: &str) -> Result<Property<'a>, Error> {
+        let property = Property::new((*self.ClassPrivate).find_property(name)).ok_or(Error::PropertyNotFound);
+
+        match property {
+            Ok(property) if T::matches(property) => Ok(property),
+            Ok(_) => Err(Error::PropertyTypeMismatch),
+            Err(e) => Err(e),
+        }
+    }
+
     pub unsafe fn process_event(
         this: *mut UObject,
         function: *mut UFunction,
@@ -314,33 +565,7 @@ impl UObject {
 
 impl Display for UObject {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-        unsafe {
-            write!(f, "{} ", (*self.ClassPrivate).name())?;
-
-            let mut outers = List::<&str, MAX_OUTERS>::new();
-            let mut outer = self.OuterPrivate;
-
-            while !outer.is_null() {
-                if outers.push((*outer).name()).is_err() {
-                    crate::log!("warning: reached outers capacity of {} for {}. outer name will be truncated.", outers.capacity(), self as *const _ as usize);
-                    break;
-                }
-
-                outer = (*outer).OuterPrivate;
-            }
-
-            for outer in outers.iter().rev() {
-                write!(f, "{}.", outer)?;
-            }
-
-            write!(f, "{}", self.name())?;
-
-            if self.NamePrivate.number() > 0 {
-                write!(f, "_{}", self.NamePrivate.number() - 1)?;
-            }
-        }
-
-        Ok(())
+        FullNameOf(self).fmt(f)
     }
 }
 
@@ -376,13 +601,72 @@ pub struct UStruct {
     pub ChildProperties: *const FField,
     pub PropertiesSize: i32,
     pub MinAlignment: i32,
-    pad1: [u8; 80],
+    pub Script: TArray<u8>,
+    pad1: [u8; PROFILE.ustruct_trailing_size],
 }
 
+// `Script` took its 16 bytes out of the padding after it, so make sure the
+// struct as a whole didn't move.
+const _: () = assert!(mem::size_of::<UStruct>() == PROFILE.ustruct_size);
+
 impl UStruct {
     pub unsafe fn is(&self, parent: *const Self) -> bool {
         self.struct_base_chain.is(&(*parent).struct_base_chain)
     }
+
+    // This struct's properties, followed by those inherited from its super
+    // structs.
+    pub fn properties(&self) -> Properties<'_> {
+        Properties::new(self, true)
+    }
+
+    // Only the properties this struct declares itself.
+    pub fn own_properties(&self) -> Properties<'_> {
+        Properties::new(self, false)
+    }
+
+    // The struct's Kismet bytecode, which only blueprint functions and the
+    // ubergraphs of blueprint classes have.
+    pub unsafe fn disassemble(&self) -> Disassembly<'_, LiveReferences> {
+        Disassembly::new(&self.Script, LiveReferences::new())
+    }
+
+    // Looks up a property of this struct or of one of its super structs by
+    // its short name, e.g. "AmmoCount". Returns null if there is none.
+    pub unsafe fn find_property(&self, name: impl ToFName) -> *const FProperty {
+        name.to_fname()
+            .and_then(|name| self.properties().find(|property| property.fname() == name))
+            .map_or(ptr::null(), |property| property.as_ptr())
+    }
+
+    // Looks up a function of this struct or of one of its super structs by its
+    // short name, e.g. "Server_RegisterHit". Returns null if there is none.
+    pub unsafe fn find_function(&self, name: impl ToFName) -> *mut UFunction {
+        let name = match name.to_fname() {
+            Some(name) => name,
+            None => return ptr::null_mut(),
+        };
+
+        let mut structure: *const UStruct = self;
+
+        while !structure.is_null() {
+            let mut field = (*structure).Children;
+
+            while !field.is_null() {
+                let object = field.cast::<UObject>();
+
+                if (*object).NamePrivate == name && (*object).fast_is(EClassCastFlags::CASTCLASS_UFunction) {
+                    return field.cast::<UFunction>() as *mut UFunction;
+                }
+
+                field = (*field).Next;
+            }
+
+            structure = (*structure).SuperStruct;
+        }
+
+        ptr::null_mut()
+    }
 }
 
 impl_deref! { UStruct as UField }
@@ -390,26 +674,106 @@ impl_deref! { UStruct as UField }
 #[repr(C)]
 pub struct UClass {
     base: UStruct,
-    pad0: [u8; 28],
+    pad0: [u8; PROFILE.uclass_size_before_class_flags],
     pub ClassFlags: EClassFlags,
     pub ClassCastFlags: EClassCastFlags,
-    pad1: [u8; 344],
+    pub ClassWithin: *mut UClass,
+    pub ClassGeneratedBy: *mut UObject,
+    pub ClassConfigName: FName,
+    ClassReps: TArray<FRepRecord>,
+    NetFields: TArray<*mut UField>,
+    FirstOwnedClassRep: i32,
+    pub ClassDefaultObject: *mut UObject,
+    SparseClassData: *mut c_void,
+    SparseClassDataStruct: *mut UStruct,
+    pub FuncMap: TMap<FName, *mut UFunction>,
+    SuperFuncMap: TMap<FName, *mut UFunction>,
+    SuperFuncMapLock: *mut c_void,
+    pub Interfaces: TArray<FImplementedInterface>,
+    pad1: [u8; PROFILE.uclass_trailing_size],
 }
 
 impl_deref! { UClass as UStruct }
 
+// A field missing from the model above would shift every field after it, so
+// check the whole against what the engine allocates.
+const _: () = assert!(mem::size_of::<UClass>() == PROFILE.uclass_size);
+
 impl UClass {
     pub fn is_blueprint_generated(&self) -> bool {
         self.ClassFlags
             .any(EClassFlags::CLASS_CompiledFromBlueprint)
     }
+
+    // The object that holds the defaults for new instances of this class.
+    // Null until the engine first needs it.
+    pub fn default_object(&self) -> *mut UObject {
+        self.ClassDefaultObject
+    }
+
+    // The interfaces this class implements itself, not counting those of its
+    // super classes.
+    pub fn interfaces(&self) -> &[FImplementedInterface] {
+        &self.Interfaces
+    }
+
+    pub fn super_class(&self) -> *mut UClass {
+        self.SuperStruct.cast()
+    }
+
+    // Looks up a function of this class or of one of its super classes by
+    // name, through each class's function map. Unlike
+    // `UStruct::find_function`, which walks the fields, this relies on the
+    // `UClass` layout past `UStruct`.
+    pub unsafe fn find_function_in_func_map(&self, name: impl ToFName) -> *mut UFunction {
+        let name = match name.to_fname() {
+            Some(name) => name,
+            None => return ptr::null_mut(),
+        };
+
+        let mut class: *const UClass = self;
+
+        while !class.is_null() {
+            if let Some(&function) = (*class).FuncMap.get(name) {
+                return function;
+            }
+
+            class = (*class).super_class();
+        }
+
+        ptr::null_mut()
+    }
+}
+
+#[repr(C)]
+struct FRepRecord {
+    Property: *mut FProperty,
+    Index: i32,
+}
+
+#[repr(C)]
+pub struct FImplementedInterface {
+    pub Class: *mut UClass,
+    // Offset of the interface's vtable pointer within an instance, or 0 for
+    // an interface implemented by a blueprint.
+    pub PointerOffset: i32,
+    pub bImplementedByK2: bool,
+}
+
+#[repr(C)]
+pub struct UEnum {
+    base: UField,
+    CppType: FString,
+    pub Names: TArray<TPair<FName, i64>>,
+    CppForm: i32,
+    EnumDisplayNameFn: usize,
 }
 
-// struct FFrame : public FOutputDevice
-// TODO: fill in from UnrealEngine\Engine\Source\Runtime\CoreUObject\Public\UObject\Stack.h
+impl_deref! { UEnum as UField }
 
 #[repr(C)]
 pub struct FOutputDevice {
+	vtable: *const usize,
 	bSuppressEventTag: bool,
 	bAutoEmitLineTerminator: bool,
 }
@@ -424,13 +788,13 @@ pub struct FFrame {
     Code: *mut u8,
 	pub Locals: *mut u8,
 
-	MostRecentProperty: *mut c_void,
-	MostRecentPropertyAddress: *mut c_void,
+	MostRecentProperty: *mut FProperty,
+	MostRecentPropertyAddress: *mut u8,
     FlowStack: crate::TArray<u32>,
-	PreviousFrame: *mut c_void,
-	OutParms: *mut c_void,
-	PropertyChainForCompiledIn: *mut c_void,
-	CurrentNativeFunction: *mut c_void,
+	PreviousFrame: *mut FFrame,
+	OutParms: *mut FOutParmRec,
+	PropertyChainForCompiledIn: *const FField,
+	CurrentNativeFunction: *mut UFunction,
 	bArrayContextFailed: bool,
 }
 
@@ -664,17 +1028,17 @@ impl_deref! { UFunction as UStruct }
 
 #[repr(C)]
 pub struct FFieldClass {
-    pad0: [u8; 8],
+    pad0: [u8; PROFILE.ffield_class_size_before_id],
     pub Id: EClassCastFlags,
     pub CastFlags: EClassCastFlags,
-    pad1: [u8; 40],
+    pad1: [u8; PROFILE.ffield_class_trailing_size],
 }
 
 #[repr(C)]
 pub struct FField {
     vtable: usize,
     pub ClassPrivate: *const FFieldClass,
-    pad0: [u8; 16],
+    pad0: [u8; PROFILE.ffield_owner_size],
     pub Next: *const FField,
     pub NamePrivate: FName,
     pub FlagsPrivate: u32,
@@ -774,9 +1138,9 @@ impl EClassFlags {
 #[repr(C)]
 pub struct UPackage {
     base: UObject,
-    unneeded_0: [u8; 56],
+    unneeded_0: [u8; PROFILE.upackage_size_before_pie_instance_id],
     pub PIEInstanceID: i32,
-    unneeded_1: [u8; 60],
+    unneeded_1: [u8; PROFILE.upackage_trailing_size],
 }
 
 impl UPackage {
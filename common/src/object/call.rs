@@ -0,0 +1,293 @@
+use super::{Error, FullNameOf, Parameter, ParameterDirection, PropertyValue, UFunction, UObject, UStruct};
+use crate::{Context, FName};
+
+use core::cell::{Cell, UnsafeCell};
+use core::marker::PhantomData;
+use core::ptr;
+
+// Reflected parameter types never ask for more than this, so a buffer with
+// this alignment holds any parameter struct.
+const MAX_PARMS_ALIGNMENT: usize = 16;
+
+// Stack storage for the parameters of one call.
+#[repr(C, align(16))]
+pub struct ParmsBuffer<const N: usize>([u8; N]);
+
+impl<const N: usize> ParmsBuffer<N> {
+    pub const fn new() -> ParmsBuffer<N> {
+        ParmsBuffer([0; N])
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.0.as_mut_ptr()
+    }
+}
+
+impl<const N: usize> Default for ParmsBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Fixed storage that several calls can carve their parameters out of at once,
+// for when they don't fit on the stack. Memory comes back only on `reset`.
+pub struct ParmsArena<const N: usize> {
+    buffer: UnsafeCell<ParmsBuffer<N>>,
+    used: Cell<usize>,
+}
+
+impl<const N: usize> ParmsArena<N> {
+    pub const fn new() -> ParmsArena<N> {
+        ParmsArena {
+            buffer: UnsafeCell::new(ParmsBuffer::new()),
+            used: Cell::new(0),
+        }
+    }
+
+    // Returns `size` zeroed bytes aligned to `alignment`, which must be a
+    // power of two no greater than 16, or null if the arena is out of space.
+    fn alloc(&self, size: usize, alignment: usize) -> *mut u8 {
+        let start = (self.used.get() + alignment - 1) & !(alignment - 1);
+
+        match start.checked_add(size) {
+            Some(end) if end <= N => {
+                self.used.set(end);
+
+                unsafe {
+                    let data = self.buffer.get().cast::<u8>().add(start);
+                    ptr::write_bytes(data, 0, size);
+                    data
+                }
+            }
+
+            _ => ptr::null_mut(),
+        }
+    }
+
+    // Frees every allocation. The borrow on `self` guarantees no call still
+    // uses one.
+    pub fn reset(&mut self) {
+        self.used.set(0);
+    }
+}
+
+impl<const N: usize> Default for ParmsArena<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// A call to a reflected function, built up at runtime instead of through a
+// parameter struct from the SDK:
+//
+//     let mut buffer = ParmsBuffer::<256>::new();
+//     let mut call = Call::new(player, "SetHealth", &mut buffer)?;
+//     call.set("NewHealth", 100.0_f32)?;
+//     call.invoke();
+//     let changed: bool = call.return_value()?;
+//
+// Strings in the parameters, whether set by `set` or written by the function,
+// are freed when the call goes away. Other parameters that own memory, like
+// arrays the function returns, are not.
+pub struct Call<'buffer> {
+    object: *mut UObject,
+    function: *mut UFunction,
+    parms: *mut u8,
+    _buffer: PhantomData<&'buffer mut [u8]>,
+}
+
+impl<'buffer> Call<'buffer> {
+    // Prepares a call of `object`'s function `name`, with its parameters in
+    // `buffer`.
+    pub unsafe fn new<const N: usize>(
+        object: *mut UObject,
+        name: &str,
+        buffer: &'buffer mut ParmsBuffer<N>,
+    ) -> Result<Call<'buffer>, Error> {
+        let function = find_function(object, name)?;
+
+        let fits = (*function).parms_size() <= N && alignment(function) <= MAX_PARMS_ALIGNMENT;
+
+        if fits {
+            let parms = buffer.as_mut_ptr();
+            ptr::write_bytes(parms, 0, (*function).parms_size());
+            Ok(Call::from_parts(object, function, parms))
+        } else {
+            Err(Error::ParmsTooLarge((*function).parms_size()))
+        }
+        .context(FullNameOf(function.cast()))
+    }
+
+    // Prepares a call of `object`'s function `name`, with its parameters in
+    // `arena`.
+    pub unsafe fn in_arena<const N: usize>(
+        object: *mut UObject,
+        name: &str,
+        arena: &'buffer ParmsArena<N>,
+    ) -> Result<Call<'buffer>, Error> {
+        let function = find_function(object, name)?;
+
+        let parms = if alignment(function) <= MAX_PARMS_ALIGNMENT {
+            arena.alloc((*function).parms_size(), alignment(function))
+        } else {
+            ptr::null_mut()
+        };
+
+        if parms.is_null() {
+            Err(Error::ParmsTooLarge((*function).parms_size()))
+        } else {
+            Ok(Call::from_parts(object, function, parms))
+        }
+        .context(FullNameOf(function.cast()))
+    }
+
+    fn from_parts(object: *mut UObject, function: *mut UFunction, parms: *mut u8) -> Call<'buffer> {
+        Call {
+            object,
+            function,
+            parms,
+            _buffer: PhantomData,
+        }
+    }
+
+    pub fn function(&self) -> *mut UFunction {
+        self.function
+    }
+
+    // Sets the parameter `name`, checking that `T` matches its type.
+    pub unsafe fn set<T: PropertyValue>(&mut self, name: &str, value: T) -> Result<(), Error> {
+        let parameter = self.typed_parameter::<T>(self.named(name))?;
+        self.write(parameter, value)
+    }
+
+    // Sets the parameter at `index` in declaration order.
+    pub unsafe fn set_at<T: PropertyValue>(&mut self, index: usize, value: T) -> Result<(), Error> {
+        let parameter = self.typed_parameter::<T>((*self.function).parameters().nth(index))?;
+        self.write(parameter, value)
+    }
+
+    // Reads the parameter `name`, e.g. an out parameter after `invoke`.
+    pub unsafe fn get<T: PropertyValue>(&self, name: &str) -> Result<T, Error> {
+        let parameter = self.typed_parameter::<T>(self.named(name))?;
+        Ok(T::read(self.parms, parameter.property))
+    }
+
+    pub unsafe fn get_at<T: PropertyValue>(&self, index: usize) -> Result<T, Error> {
+        let parameter = self.typed_parameter::<T>((*self.function).parameters().nth(index))?;
+        Ok(T::read(self.parms, parameter.property))
+    }
+
+    pub unsafe fn return_value<T: PropertyValue>(&self) -> Result<T, Error> {
+        let parameter = (*self.function)
+            .parameters()
+            .find(|parameter| parameter.direction == ParameterDirection::Return);
+
+        let parameter = self.typed_parameter::<T>(parameter)?;
+        Ok(T::read(self.parms, parameter.property))
+    }
+
+    pub unsafe fn invoke(&mut self) {
+        UObject::process_event(self.object, self.function, self.parms.cast());
+    }
+
+    unsafe fn write<T: PropertyValue>(&mut self, parameter: Parameter, value: T) -> Result<(), Error> {
+        if value.accepts(parameter.property) {
+            value.write(self.parms, parameter.property);
+            Ok(())
+        } else {
+            Err(Error::PropertyTypeMismatch)
+        }
+    }
+
+    unsafe fn named(&self, name: &str) -> Option<Parameter<'buffer>> {
+        let name = FName::find(name)?;
+
+        (*self.function)
+            .parameters()
+            .find(|parameter| parameter.property.fname() == name)
+    }
+
+    unsafe fn typed_parameter<T: PropertyValue>(
+        &self,
+        parameter: Option<Parameter<'buffer>>,
+    ) -> Result<Parameter<'buffer>, Error> {
+        match parameter {
+            Some(parameter) if T::matches(parameter.property) => Ok(parameter),
+            Some(_) => Err(Error::PropertyTypeMismatch),
+            None => Err(Error::ParameterNotFound),
+        }
+    }
+}
+
+impl<'buffer> Drop for Call<'buffer> {
+    fn drop(&mut self) {
+        unsafe {
+            (*self.function).destroy_parameters(self.parms);
+        }
+    }
+}
+
+unsafe fn find_function(object: *mut UObject, name: &str) -> Result<*mut UFunction, Error> {
+    // Walk the class's fields, which unlike `UClass::FuncMap` don't depend on
+    // modelling `UClass` past its `UStruct` base.
+    let class = &*(*object).ClassPrivate.cast::<UStruct>();
+    let function = class.find_function(name);
+
+    if function.is_null() {
+        Err(Error::FunctionNotFound)
+    } else {
+        Ok(function)
+    }
+}
+
+unsafe fn alignment(function: *const UFunction) -> usize {
+    // Native functions leave this at 0.
+    ((*function.cast::<UStruct>()).MinAlignment as usize).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arena_aligns_each_allocation() {
+        let arena = ParmsArena::<64>::new();
+
+        let first = arena.alloc(3, 1);
+        let second = arena.alloc(8, 8);
+        let third = arena.alloc(4, 16);
+
+        assert!(!first.is_null() && !second.is_null() && !third.is_null());
+        assert_eq!(second as usize % 8, 0);
+        assert_eq!(third as usize % 16, 0);
+        assert_eq!(second as usize - first as usize, 8);
+        assert_eq!(third as usize - first as usize, 16);
+    }
+
+    #[test]
+    fn arena_zeroes_allocations() {
+        let mut arena = ParmsArena::<16>::new();
+
+        let data = arena.alloc(16, 1);
+        unsafe { ptr::write_bytes(data, 0xAA, 16) };
+
+        arena.reset();
+
+        let data = arena.alloc(16, 1);
+        assert!(unsafe { core::slice::from_raw_parts(data, 16) }.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn arena_runs_out_of_space() {
+        let mut arena = ParmsArena::<16>::new();
+
+        assert!(!arena.alloc(12, 4).is_null());
+        assert!(arena.alloc(8, 4).is_null());
+        assert!(!arena.alloc(4, 4).is_null());
+        assert!(arena.alloc(1, 1).is_null());
+        assert!(arena.alloc(usize::MAX, 1).is_null());
+
+        arena.reset();
+        assert!(!arena.alloc(16, 16).is_null());
+    }
+}
@@ -0,0 +1,116 @@
+use core::fmt::{self, Display, Formatter};
+use core::ops::BitOr;
+
+// Declares a flags type along with a table of its flag names for Display.
+macro_rules! flags {
+    ($(#[$attr:meta])* $Flags:ident($Repr:ty) { $($Flag:ident = $value:expr,)* }) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        #[repr(transparent)]
+        pub struct $Flags(pub $Repr);
+
+        impl $Flags {
+            $(pub const $Flag: Self = Self($value);)*
+
+            const NAMES: &'static [(Self, &'static str)] = &[$((Self::$Flag, stringify!($Flag)),)*];
+
+            pub fn any(&self, Self(flags): Self) -> bool {
+                self.0 & flags != 0
+            }
+
+            pub fn contains(&self, Self(flags): Self) -> bool {
+                self.0 & flags == flags
+            }
+        }
+
+        impl BitOr for $Flags {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        // Writes the set flags separated by " | ", e.g. "RF_Public | RF_Transient",
+        // followed by any bits that have no name.
+        impl Display for $Flags {
+            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+                if self.0 == 0 {
+                    let none = Self::NAMES.iter().find(|(flag, _)| flag.0 == 0);
+                    return none.map_or("0", |(_, name)| name).fmt(f);
+                }
+
+                let mut unnamed = self.0;
+                let mut separator = "";
+
+                for (flag, name) in Self::NAMES.iter().filter(|(flag, _)| flag.0 != 0) {
+                    if self.contains(*flag) {
+                        write!(f, "{}{}", separator, name)?;
+                        unnamed &= !flag.0;
+                        separator = " | ";
+                    }
+                }
+
+                if unnamed != 0 {
+                    write!(f, "{}{:#x}", separator, unnamed)?;
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+flags! {
+    // Engine\Source\Runtime\CoreUObject\Public\UObject\ObjectMacros.h
+    EObjectFlags(u32) {
+        RF_NoFlags = 0x0,
+        RF_Public = 0x1,
+        RF_Standalone = 0x2,
+        RF_MarkAsNative = 0x4,
+        RF_Transactional = 0x8,
+        RF_ClassDefaultObject = 0x10,
+        RF_ArchetypeObject = 0x20,
+        RF_Transient = 0x40,
+        RF_MarkAsRootSet = 0x80,
+        RF_TagGarbageTemp = 0x100,
+        RF_NeedInitialization = 0x200,
+        RF_NeedLoad = 0x400,
+        RF_KeepForCooker = 0x800,
+        RF_NeedPostLoad = 0x1000,
+        RF_NeedPostLoadSubobjects = 0x2000,
+        RF_NewerVersionExists = 0x4000,
+        RF_BeginDestroyed = 0x8000,
+        RF_FinishDestroyed = 0x10000,
+        RF_BeingRegenerated = 0x20000,
+        RF_DefaultSubObject = 0x40000,
+        RF_WasLoaded = 0x80000,
+        RF_TextExportTransient = 0x100000,
+        RF_LoadCompleted = 0x200000,
+        RF_InheritableComponentTemplate = 0x400000,
+        RF_DuplicateTransient = 0x800000,
+        RF_StrongRefOnFrame = 0x1000000,
+        RF_NonPIEDuplicateTransient = 0x2000000,
+        RF_Dynamic = 0x4000000,
+        RF_WillBeLoaded = 0x8000000,
+        RF_HasExternalPackage = 0x10000000,
+    }
+}
+
+flags! {
+    // The flags in `FUObjectItem`, which the garbage collector and the async
+    // loader maintain.
+    // Engine\Source\Runtime\CoreUObject\Public\UObject\ObjectMacros.h
+    EInternalObjectFlags(u32) {
+        None = 0,
+        ReachableInCluster = 1 << 23,
+        ClusterRoot = 1 << 24,
+        Native = 1 << 25,
+        Async = 1 << 26,
+        AsyncLoading = 1 << 27,
+        Unreachable = 1 << 28,
+        PendingKill = 1 << 29,
+        RootSet = 1 << 30,
+        PendingConstruction = 1 << 31,
+    }
+}
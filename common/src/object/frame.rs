@@ -0,0 +1,398 @@
+use super::{
+    EPropertyFlags, Error, FFrame, FNativeFuncPtr, FProperty, Parameter, ParameterDirection, Parameters,
+    ParmsBuffer, Property, PropertyValue, UFunction, UObject,
+};
+use crate::Context;
+
+use core::iter;
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+
+// The engine's bytecode handlers, indexed by `EExprToken`. The hook looks for
+// the table in `FFrame::Step` while it initializes. Without it, arguments in
+// bytecode can't be evaluated, and reading them fails.
+#[allow(non_upper_case_globals)]
+pub static mut GNatives: *const [FNativeFuncPtr; 0x100] = ptr::null();
+
+// The largest argument that `Arguments` can evaluate out of bytecode.
+const MAX_ARGUMENT_SIZE: usize = 256;
+
+type Temporary = ParmsBuffer<MAX_ARGUMENT_SIZE>;
+
+// Where a caller wants an out parameter written, when that isn't in `Locals`.
+#[repr(C)]
+pub struct FOutParmRec {
+    pub Property: *mut FProperty,
+    pub PropAddr: *mut u8,
+    pub NextOutParm: *mut FOutParmRec,
+}
+
+impl FFrame {
+    // The function whose bytecode and locals this frame holds.
+    pub fn node(&self) -> *mut UFunction {
+        self.Node
+    }
+
+    pub fn object(&self) -> *mut UObject {
+        self.Object
+    }
+
+    pub unsafe fn out_parms(&self) -> impl Iterator<Item = &FOutParmRec> {
+        let mut out = self.OutParms;
+
+        iter::from_fn(move || {
+            let current = out.as_ref()?;
+            out = current.NextOutParm;
+            Some(current)
+        })
+    }
+
+    // Where the caller wants `property`'s value written, or null if it's not
+    // an out parameter of this call.
+    pub unsafe fn out_parm(&self, property: *const FProperty) -> *mut u8 {
+        self.out_parms()
+            .find(|out| ptr::eq(out.Property, property))
+            .map_or(ptr::null_mut(), |out| out.PropAddr)
+    }
+
+    // Reads the arguments of a call to the native `function` that this frame
+    // is running.
+    pub unsafe fn arguments<'frame>(&'frame mut self, function: &'frame UFunction) -> Arguments<'frame> {
+        Arguments {
+            code: self.Code,
+            most_recent_property: self.MostRecentProperty,
+            most_recent_property_address: self.MostRecentPropertyAddress,
+            frame: self,
+            parameters: function.parameters(),
+            is_finished: false,
+        }
+    }
+
+    // The parameter struct of `function`, which this frame is calling.
+    //
+    // Through `ProcessEvent`, or for a script function, that's `Locals`. A
+    // native function called from bytecode finds its arguments in its
+    // caller's `Code` instead, so they're evaluated into a copy in `buffer`
+    // and `Code` is rewound for the original function to read them again.
+    // Writing to the copy doesn't change what the caller passed. The copy owns
+    // its strings, so free them with `UFunction::destroy_parameters` once
+    // it's no longer needed.
+    pub unsafe fn parameters<T, const N: usize>(
+        &mut self,
+        function: &UFunction,
+        buffer: &mut ParmsBuffer<N>,
+    ) -> Result<*mut T, Error> {
+        self.parameters_in(function, buffer.as_mut_ptr(), N)
+    }
+
+    // Like `parameters`, with the copy in the `capacity` bytes at `parms`.
+    unsafe fn parameters_in<T>(&mut self, function: &UFunction, parms: *mut u8, capacity: usize) -> Result<*mut T, Error> {
+        if self.is_running(function) {
+            return Ok(self.Locals.cast());
+        }
+
+        let size = function.parms_size().max(mem::size_of::<T>());
+
+        if size > capacity {
+            return Err(Error::ParmsTooLarge(size));
+        }
+
+        ptr::write_bytes(parms, 0, size);
+
+        if let Err(e) = self.arguments(function).read_into(parms) {
+            function.destroy_parameters(parms);
+            return Err(e);
+        }
+
+        Ok(parms.cast())
+    }
+
+    // Whether `function`'s parameters are in `Locals`, rather than in argument
+    // expressions in `Code`.
+    pub fn is_running(&self, function: &UFunction) -> bool {
+        ptr::eq(self.Node, function)
+    }
+}
+
+// The parameters of a hooked call, read only if the hook asks for them.
+//
+// Through `ProcessEvent`, or for a script function, that's the caller's
+// parameter struct. For a native function called from bytecode, `get`
+// evaluates the argument expressions into a copy, and the original function
+// evaluates them again, so any side effects they have happen twice.
+pub struct HookParameters<'frame, T> {
+    frame: &'frame mut FFrame,
+    function: &'frame UFunction,
+    copy: MaybeUninit<T>,
+    place: Place<T>,
+}
+
+enum Place<T> {
+    Unread,
+    Locals(*mut T),
+    Copy,
+    Unreadable,
+}
+
+impl<'frame, T> HookParameters<'frame, T> {
+    pub fn new(frame: &'frame mut FFrame, function: &'frame UFunction) -> HookParameters<'frame, T> {
+        HookParameters {
+            frame,
+            function,
+            copy: MaybeUninit::uninit(),
+            place: Place::Unread,
+        }
+    }
+
+    // Whether `get` would have to evaluate argument expressions.
+    pub fn is_from_bytecode(&self) -> bool {
+        !self.frame.is_running(self.function)
+    }
+
+    // The parameters, or `None` if they couldn't be read out of bytecode.
+    pub unsafe fn get(&mut self) -> Option<&mut T> {
+        if let Place::Unread = self.place {
+            let copy = self.copy.as_mut_ptr();
+
+            self.place = match self.frame.parameters_in::<T>(self.function, copy.cast(), mem::size_of::<T>()) {
+                Ok(parameters) if parameters == copy => Place::Copy,
+                Ok(parameters) => Place::Locals(parameters),
+                Err(_) => Place::Unreadable,
+            };
+        }
+
+        match self.place {
+            Place::Locals(parameters) => parameters.as_mut(),
+            Place::Copy => Some(&mut *self.copy.as_mut_ptr()),
+            Place::Unread | Place::Unreadable => None,
+        }
+    }
+
+    // The hooked call's frame, e.g. to read its arguments one at a time.
+    pub fn frame(&mut self) -> &mut FFrame {
+        self.frame
+    }
+}
+
+impl<'frame, T> Drop for HookParameters<'frame, T> {
+    fn drop(&mut self) {
+        if let Place::Copy = self.place {
+            unsafe {
+                self.function.destroy_parameters(self.copy.as_mut_ptr().cast());
+            }
+        }
+    }
+}
+
+// Reads a native function's arguments in declaration order, like the engine's
+// P_GET_* macros.
+//
+// Called through `ProcessEvent`, a native function finds its arguments at
+// their offsets in `Locals`. Called from bytecode, it runs on its caller's
+// frame, and each argument is an expression in the caller's `Code` that has
+// to be evaluated.
+//
+// Dropping the reader rewinds `Code`, so a hook can read the arguments and
+// still call the original function, which reads them again. Any side effects
+// of the argument expressions then happen twice. A hook that replaces the
+// original instead calls `finish`.
+pub struct Arguments<'frame> {
+    frame: &'frame mut FFrame,
+    parameters: Parameters<'frame>,
+    code: *mut u8,
+    most_recent_property: *mut FProperty,
+    most_recent_property_address: *mut u8,
+    is_finished: bool,
+}
+
+// Where an evaluated argument ended up.
+struct Stepped {
+    // Always valid to read, but may be a temporary copy.
+    value: *const u8,
+
+    // Whether `value` is the temporary, which owns any strings in it.
+    is_temporary: bool,
+
+    // The caller's variable, or null if the argument isn't one.
+    address: *mut u8,
+}
+
+impl<'frame> Arguments<'frame> {
+    pub unsafe fn next<T: PropertyValue>(&mut self) -> Result<T, Error> {
+        let mut temporary = Temporary::new();
+        let (property, stepped) = self.step::<T>(&mut temporary)?;
+        let value = T::read_value(stepped.value, property);
+        stepped.discard(property);
+        Ok(value)
+    }
+
+    // The caller's variable behind a reference or out parameter, like the
+    // engine's P_GET_*_REF macros.
+    pub unsafe fn next_ref<T: PropertyValue>(&mut self) -> Result<OutArgument<'frame, T>, Error> {
+        let mut temporary = Temporary::new();
+        let (property, stepped) = self.step::<T>(&mut temporary)?;
+        stepped.discard(property);
+
+        if stepped.address.is_null() {
+            Err(Error::ArgumentNotAddressable).context(format_args!("parameter {}", property.name()))
+        } else {
+            Ok(OutArgument {
+                address: stepped.address,
+                property,
+                value: PhantomData,
+            })
+        }
+    }
+
+    // Steps over an argument without reading it.
+    pub unsafe fn skip(&mut self) -> Result<(), Error> {
+        let property = self.next_parameter().ok_or(Error::ParameterNotFound)?.property;
+        let mut temporary = Temporary::new();
+        self.evaluate(property, &mut temporary)?.discard(property);
+        Ok(())
+    }
+
+    // Evaluates the remaining arguments into their places in `parms`, a
+    // parameter struct of the function, which takes over their strings.
+    pub unsafe fn read_into(&mut self, parms: *mut u8) -> Result<(), Error> {
+        let mut temporary = Temporary::new();
+
+        while let Some(parameter) = self.next_parameter() {
+            let stepped = self.evaluate(parameter.property, &mut temporary)?;
+            ptr::copy_nonoverlapping(stepped.value, parms.add(parameter.offset()), parameter.size());
+        }
+
+        Ok(())
+    }
+
+    // Steps over the arguments that weren't read and the end of the argument
+    // list, leaving `Code` after the call like the engine's P_FINISH, for a
+    // hook that doesn't call the original.
+    pub unsafe fn finish(mut self) -> Result<(), Error> {
+        let mut temporary = Temporary::new();
+
+        while let Some(parameter) = self.next_parameter() {
+            self.evaluate(parameter.property, &mut temporary)?.discard(parameter.property);
+        }
+
+        // EX_EndFunctionParms
+        if !self.frame.Code.is_null() {
+            self.frame.Code = self.frame.Code.add(1);
+        }
+
+        self.is_finished = true;
+        Ok(())
+    }
+
+    unsafe fn step<T: PropertyValue>(
+        &mut self,
+        temporary: &mut Temporary,
+    ) -> Result<(Property<'frame>, Stepped), Error> {
+        let property = self.next_parameter().ok_or(Error::ParameterNotFound)?.property;
+
+        if !T::matches(property) {
+            return Err(Error::PropertyTypeMismatch).context(format_args!("parameter {}", property.name()));
+        }
+
+        let stepped = self.evaluate(property, temporary)?;
+        Ok((property, stepped))
+    }
+
+    // The caller passes every parameter but the return value.
+    fn next_parameter(&mut self) -> Option<Parameter<'frame>> {
+        self.parameters
+            .by_ref()
+            .find(|parameter| parameter.direction != ParameterDirection::Return)
+    }
+
+    unsafe fn evaluate(&mut self, property: Property, temporary: &mut Temporary) -> Result<Stepped, Error> {
+        let frame = &mut *self.frame;
+
+        if frame.Code.is_null() {
+            // FFrame::StepExplicitProperty
+            let mut address = ptr::null_mut();
+
+            if property.flags().contains(EPropertyFlags::CPF_OutParm) {
+                address = frame.out_parm(property.as_ptr());
+            }
+
+            if address.is_null() {
+                address = frame.Locals.add(property.offset());
+            }
+
+            return Ok(Stepped {
+                value: address,
+                is_temporary: false,
+                address,
+            });
+        }
+
+        if property.size() > MAX_ARGUMENT_SIZE {
+            return Err(Error::ParmsTooLarge(property.size()));
+        }
+
+        let natives = (*ptr::addr_of!(GNatives)).as_ref().ok_or(Error::NativesNotFound)?;
+
+        // FFrame::Step. Expressions that name a variable leave its address in
+        // `MostRecentPropertyAddress`.
+        let token = *frame.Code;
+        frame.Code = frame.Code.add(1);
+        frame.MostRecentPropertyAddress = ptr::null_mut();
+
+        // Each argument starts from zeroes, like the engine's stack
+        // temporaries, so a string isn't mistaken for one that's owned.
+        let result = temporary.as_mut_ptr();
+        ptr::write_bytes(result, 0, property.size());
+        (natives[usize::from(token)])(frame.Object, frame, result.cast());
+
+        Ok(Stepped {
+            value: result,
+            is_temporary: true,
+            address: frame.MostRecentPropertyAddress,
+        })
+    }
+}
+
+impl Stepped {
+    // Frees what evaluating the argument copied into the temporary, once
+    // nothing refers to it.
+    unsafe fn discard(&self, property: Property) {
+        if self.is_temporary {
+            property.destroy_value(self.value as *mut u8);
+        }
+    }
+}
+
+impl<'frame> Drop for Arguments<'frame> {
+    fn drop(&mut self) {
+        if !self.is_finished {
+            self.frame.Code = self.code;
+            self.frame.MostRecentProperty = self.most_recent_property;
+            self.frame.MostRecentPropertyAddress = self.most_recent_property_address;
+        }
+    }
+}
+
+// The caller's variable behind a reference or out parameter. Writes go
+// straight to the caller.
+pub struct OutArgument<'frame, T> {
+    address: *mut u8,
+    property: Property<'frame>,
+    value: PhantomData<T>,
+}
+
+impl<'frame, T: PropertyValue> OutArgument<'frame, T> {
+    pub unsafe fn get(&self) -> T {
+        T::read_value(self.address, self.property)
+    }
+
+    pub unsafe fn set(&mut self, value: T) -> Result<(), Error> {
+        if !value.accepts(self.property) {
+            return Err(Error::PropertyTypeMismatch);
+        }
+
+        value.write_value(self.address, self.property);
+        Ok(())
+    }
+}
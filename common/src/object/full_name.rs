@@ -1,6 +1,9 @@
 use crate::list::{self, List};
-use crate::split::ReverseSplitIterator;
+use crate::name::split_number;
+use crate::FName;
+
 use core::convert::TryFrom;
+use core::iter;
 
 #[derive(macros::NoPanicErrorDebug)]
 pub enum Error {
@@ -9,10 +12,37 @@ pub enum Error {
     List(#[from] list::Error),
 }
 
+// A full name in the form that `UObject`'s Display writes:
+//
+//     Class /Script/Package.Outer:Subobject.Name_2
+//
+// The class comes first. Then the path runs from the package in to the object,
+// with each name separated by '.', or by ':' where the engine marks a
+// subobject. A trailing "_N" on any name is its number, as in `FName`.
 pub struct FullName<'name, const NUM_OUTERS: usize> {
-    pub name: &'name [u8],
+    pub name: NamePart<'name>,
     pub class: &'name [u8],
-    pub outers: List<&'name [u8], NUM_OUTERS>,
+    // Innermost first.
+    pub outers: List<NamePart<'name>, NUM_OUTERS>,
+}
+
+// One name of a path, split into text and number the way the engine splits an
+// `FName`: "Default__Foo_C_2" is "Default__Foo_C" with number 3.
+#[derive(Copy, Clone)]
+pub struct NamePart<'name> {
+    pub text: &'name [u8],
+    pub number: u32,
+}
+
+impl<'name> NamePart<'name> {
+    fn new(part: &'name [u8]) -> NamePart<'name> {
+        let (text, number) = split_number(part);
+        NamePart { text, number }
+    }
+
+    pub unsafe fn matches(&self, name: FName) -> bool {
+        name.number() == self.number && name.text().as_bytes() == self.text
+    }
 }
 
 fn split_class_and_outers(name: &str) -> Result<(&[u8], &[u8]), Error> {
@@ -24,29 +54,101 @@ fn split_class_and_outers(name: &str) -> Result<(&[u8], &[u8]), Error> {
     Ok((&name[..space], &name[space + 1..]))
 }
 
+// Splits a path on both delimiters, starting from the end. A '.' and a ':' are
+// interchangeable here, because which one the engine writes follows from the
+// classes of the outers, which a match checks anyway.
+fn reverse_split_path(path: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut rest = Some(path);
+
+    iter::from_fn(move || {
+        let path = rest?;
+
+        match path.iter().rposition(|c| *c == b'.' || *c == b':') {
+            Some(split) => {
+                rest = path.get(..split);
+                path.get(split + 1..)
+            }
+
+            None => {
+                rest = None;
+                Some(path)
+            }
+        }
+    })
+}
+
 impl<'name, const NUM_OUTERS: usize> TryFrom<&'name str> for FullName<'name, NUM_OUTERS> {
     type Error = Error;
 
-    fn try_from(full_name: &str) -> Result<FullName<NUM_OUTERS>, Self::Error> {
-        let (class, outers) = split_class_and_outers(full_name)?;
+    fn try_from(full_name: &'name str) -> Result<FullName<'name, NUM_OUTERS>, Self::Error> {
+        let (class, path) = split_class_and_outers(full_name)?;
 
         // Reverse split because outers are organized inside-out within an
         // object.
-        let mut outers = ReverseSplitIterator::new(outers, b'.');
+        let mut parts = reverse_split_path(path).map(NamePart::new);
 
         // The first "outer" in the input name is actually the object name.
-        let name = outers.next().ok_or(Error::NoName)?;
+        let name = parts.next().filter(|name| !name.text.is_empty()).ok_or(Error::NoName)?;
 
-        let mut list = List::new();
+        let mut outers = List::new();
 
-        for outer in outers {
-            list.push(outer)?;
+        for outer in parts {
+            outers.push(outer)?;
         }
 
         Ok(FullName {
             name,
             class,
-            outers: list,
+            outers,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reverse_split_path_yields_innermost_first() {
+        let parts = reverse_split_path(b"/Script/FSD.Item:Mesh.Default");
+        assert!(parts.eq([&b"Default"[..], b"Mesh", b"Item", b"/Script/FSD"]));
+    }
+
+    #[test]
+    fn reverse_split_path_keeps_empty_parts() {
+        assert!(reverse_split_path(b"Outer.").eq([&b""[..], b"Outer"]));
+        assert!(reverse_split_path(b"A..B").eq([&b"B"[..], b"", b"A"]));
+        assert!(reverse_split_path(b"").eq([&b""[..]]));
+    }
+
+    #[test]
+    fn reverse_split_path_of_a_single_name() {
+        assert!(reverse_split_path(b"/Script/Engine").eq([&b"/Script/Engine"[..]]));
+    }
+
+    #[test]
+    fn full_name_splits_class_name_and_outers() {
+        let name = FullName::<4>::try_from("Function /Script/FSD.Item:Sub.GetItemName_2");
+
+        let name = match name {
+            Ok(name) => name,
+            Err(e) => panic!("{:?}", e),
+        };
+
+        assert_eq!(name.class, b"Function");
+        assert_eq!(name.name.text, b"GetItemName");
+        assert_eq!(name.name.number, 3);
+        assert_eq!(name.outers.len(), 3);
+        assert!(name.outers.iter().map(|outer| outer.text).eq([&b"Sub"[..], b"Item", b"/Script/FSD"]));
+    }
+
+    #[test]
+    fn full_name_needs_a_class_and_a_name() {
+        assert!(matches!(
+            FullName::<4>::try_from("/Script/FSD.Item"),
+            Err(Error::NoSpaceBetweenClassAndOuters)
+        ));
+
+        assert!(matches!(FullName::<4>::try_from("Class /Script/FSD."), Err(Error::NoName)));
+    }
+}
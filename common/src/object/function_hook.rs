@@ -0,0 +1,38 @@
+use crate::{FNativeFuncPtr, UFunction};
+
+pub struct UFunctionHook {
+    function: *mut UFunction,
+    original: FNativeFuncPtr,
+}
+
+impl UFunctionHook {
+    pub unsafe fn new(
+        function: *mut UFunction,
+        where_to_place_original: *mut FNativeFuncPtr,
+        hook: FNativeFuncPtr,
+    ) -> Option<UFunctionHook> {
+        if function.is_null() || Self::is_hooked(function, hook) {
+            return None;
+        }
+
+        let original = (*function).Func;
+        *where_to_place_original = original;
+        (*function).Func = hook;
+
+        Some(UFunctionHook { function, original })
+    }
+
+    // Whether `hook` already replaces `function`'s native implementation.
+    // Hooking it again would save `hook` as the original.
+    pub unsafe fn is_hooked(function: *const UFunction, hook: FNativeFuncPtr) -> bool {
+        (*function).Func as usize == hook as usize
+    }
+}
+
+impl Drop for UFunctionHook {
+    fn drop(&mut self) {
+        unsafe {
+            (*self.function).Func = self.original;
+        }
+    }
+}
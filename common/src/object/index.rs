@@ -0,0 +1,140 @@
+use super::full_name::FullName;
+use super::{FUObjectArray, UObject};
+use crate::hash_index::HashIndex;
+use crate::FnvHasher;
+
+use core::hash::Hasher;
+use core::hint;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Slots in the table. Keep it well above the number of objects in the game so
+// probes stay short. Each slot is 8 bytes.
+const CAPACITY: usize = 1 << 20;
+
+static mut INDEX: ObjectIndex = ObjectIndex::new();
+
+// Whether someone holds an `IndexGuard`.
+static IS_LOCKED: AtomicBool = AtomicBool::new(false);
+
+// Exclusive access to the shared index until dropped. `FUObjectArray::find`
+// runs on whichever thread calls it, like the game thread inside a hook while
+// our own thread is looking objects up, so every use of the index goes
+// through this.
+pub struct IndexGuard(());
+
+impl IndexGuard {
+    // Spins until no other thread holds the index. Lookups are short, so
+    // there's no need to sleep.
+    pub fn lock() -> IndexGuard {
+        while IS_LOCKED
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+
+        IndexGuard(())
+    }
+}
+
+impl Deref for IndexGuard {
+    type Target = ObjectIndex;
+
+    fn deref(&self) -> &ObjectIndex {
+        unsafe { &*ptr::addr_of!(INDEX) }
+    }
+}
+
+impl DerefMut for IndexGuard {
+    fn deref_mut(&mut self) -> &mut ObjectIndex {
+        unsafe { &mut *ptr::addr_of_mut!(INDEX) }
+    }
+}
+
+impl Drop for IndexGuard {
+    fn drop(&mut self) {
+        IS_LOCKED.store(false, Ordering::Release);
+    }
+}
+
+// A hash table from an object's name and outers to its index in
+// `GUObjectArray`, so `FUObjectArray::find` doesn't compare names against
+// every object.
+//
+// Objects are indexed as the object array grows. Entries aren't removed when
+// objects die, so a hit is only a candidate that the caller still has to
+// compare against the name it looked up.
+pub struct ObjectIndex {
+    table: HashIndex<CAPACITY>,
+    num_indexed: usize,
+}
+
+impl ObjectIndex {
+    pub const fn new() -> ObjectIndex {
+        ObjectIndex {
+            table: HashIndex::new(),
+            num_indexed: 0,
+        }
+    }
+
+    // Indexes the objects added to `objects` since the last update.
+    pub unsafe fn update(&mut self, objects: &FUObjectArray) {
+        let num_objects = objects.ObjObjects.NumElements as usize;
+
+        while self.num_indexed < num_objects {
+            let index = self.num_indexed;
+            let item = objects.index_to_object(index as i32);
+
+            if !item.is_null() && !(*item).Object.is_null() && !self.insert(hash_object((*item).Object), index) {
+                // The table is full. Lookups fall back to a scan for the
+                // objects that didn't fit.
+                break;
+            }
+
+            self.num_indexed += 1;
+        }
+    }
+
+    // Returns whether there was room for the object.
+    pub fn insert(&mut self, hash: u64, index: usize) -> bool {
+        self.table.insert(hash, index as u32)
+    }
+
+    // Yields the index of each object whose name and outers may hash to
+    // `hash`.
+    pub fn candidates(&self, hash: u64) -> impl Iterator<Item = usize> + '_ {
+        self.table.candidates(hash).map(|index| index as usize)
+    }
+}
+
+// Hashes the text of an object's name and of each of its outers.
+pub unsafe fn hash_object(object: *const UObject) -> u64 {
+    let mut hasher = FnvHasher::new();
+    hasher.write((*object).name().as_bytes());
+
+    let mut outer = (*object).OuterPrivate;
+
+    while !outer.is_null() {
+        hasher.write(b".");
+        hasher.write((*outer).name().as_bytes());
+        outer = (*outer).OuterPrivate;
+    }
+
+    hasher.finish()
+}
+
+// Hashes the same bytes as `hash_object` does for the object `name` refers to.
+// Numbers and delimiters are left out, so a match still has to compare them.
+pub fn hash_full_name<const NUM_OUTERS: usize>(name: &FullName<NUM_OUTERS>) -> u64 {
+    let mut hasher = FnvHasher::new();
+    hasher.write(name.name.text);
+
+    for outer in name.outers.iter() {
+        hasher.write(b".");
+        hasher.write(outer.text);
+    }
+
+    hasher.finish()
+}
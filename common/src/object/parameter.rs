@@ -0,0 +1,122 @@
+use super::{EPropertyFlags, Properties, Property, UFunction};
+
+// How a parameter passes data between caller and callee.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum ParameterDirection {
+    // Read by the callee.
+    In,
+    // Written by the callee.
+    Out,
+    // Passed by non-const reference, so read and written by the callee.
+    Ref,
+    // The return value.
+    Return,
+}
+
+impl ParameterDirection {
+    // Whether the callee writes the parameter, so the caller reads it back.
+    pub fn is_output(&self) -> bool {
+        !matches!(self, Self::In)
+    }
+}
+
+impl EPropertyFlags {
+    // The direction of a function's property with these flags, or `None` if
+    // the property is a local variable of a blueprint function rather than a
+    // parameter. A const reference counts as an input.
+    pub fn parameter_direction(&self) -> Option<ParameterDirection> {
+        if self.contains(Self::CPF_ReturnParm) {
+            Some(ParameterDirection::Return)
+        } else if self.contains(Self::CPF_OutParm) && !self.contains(Self::CPF_ConstParm) {
+            if self.contains(Self::CPF_ReferenceParm) {
+                Some(ParameterDirection::Ref)
+            } else {
+                Some(ParameterDirection::Out)
+            }
+        } else if self.contains(Self::CPF_Parm) {
+            Some(ParameterDirection::In)
+        } else {
+            None
+        }
+    }
+
+    // Whether a function's property with these flags is one of the function's
+    // inputs or outputs, rather than a local variable of a blueprint function.
+    pub fn is_parameter(&self) -> bool {
+        self.parameter_direction().is_some()
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Parameter<'a> {
+    pub property: Property<'a>,
+    pub direction: ParameterDirection,
+}
+
+impl<'a> Parameter<'a> {
+    pub fn name(&self) -> &'a str {
+        self.property.name()
+    }
+
+    // Byte offset of the parameter within the function's parameter struct.
+    pub fn offset(&self) -> usize {
+        self.property.offset()
+    }
+
+    pub fn size(&self) -> usize {
+        self.property.size()
+    }
+}
+
+// Iterates a function's parameters in declaration order, which is their order
+// in the parameter struct, with the return value last.
+pub struct Parameters<'a> {
+    properties: Properties<'a>,
+}
+
+impl<'a> Iterator for Parameters<'a> {
+    type Item = Parameter<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.properties.find_map(|property| {
+            property
+                .flags()
+                .parameter_direction()
+                .map(|direction| Parameter { property, direction })
+        })
+    }
+}
+
+impl UFunction {
+    pub fn num_parms(&self) -> usize {
+        usize::from(self.NumParms)
+    }
+
+    // Size in bytes of the function's parameter struct.
+    pub fn parms_size(&self) -> usize {
+        usize::from(self.ParmsSize)
+    }
+
+    pub fn return_value_offset(&self) -> Option<usize> {
+        // The engine stores MAX_uint16 for a function that returns nothing.
+        if self.ReturnValueOffset == u16::MAX {
+            None
+        } else {
+            Some(usize::from(self.ReturnValueOffset))
+        }
+    }
+
+    pub fn parameters(&self) -> Parameters<'_> {
+        Parameters {
+            properties: self.own_properties(),
+        }
+    }
+
+    // Frees the strings in a parameter struct of the function, as
+    // `Property::destroy_value` does.
+    pub unsafe fn destroy_parameters(&self, parms: *mut u8) {
+        for parameter in self.parameters() {
+            parameter.property.destroy_value(parms.add(parameter.offset()));
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::UObject;
+
+use core::fmt::{self, Display, Formatter, Write};
+use core::ptr;
+
+// Formatters for an object's names. They hold a pointer rather than borrow, so
+// they're `Copy` and can be stored in errors or passed straight to `log!`.
+
+// "Class /Script/Package.Outer:Name", the same as `UObject`'s Display.
+#[derive(Copy, Clone)]
+pub struct FullNameOf(pub *const UObject);
+
+impl Display for FullNameOf {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        unsafe {
+            write!(f, "{} {}", (*(*self.0).ClassPrivate).name(), PathNameOf::new(self.0))
+        }
+    }
+}
+
+// "/Script/Package.Outer:Name": the full name without the class.
+#[derive(Copy, Clone)]
+pub struct PathNameOf {
+    object: *const UObject,
+    stop_outer: *const UObject,
+}
+
+impl PathNameOf {
+    pub fn new(object: *const UObject) -> PathNameOf {
+        PathNameOf {
+            object,
+            stop_outer: ptr::null(),
+        }
+    }
+
+    // The path below `outer`, e.g. "Outer:Name" relative to the package. Like
+    // the engine, this is the whole path if `outer` isn't one of the object's
+    // outers.
+    pub fn relative_to(object: *const UObject, outer: *const UObject) -> PathNameOf {
+        PathNameOf {
+            object,
+            stop_outer: outer,
+        }
+    }
+}
+
+impl Display for PathNameOf {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        unsafe { write_path(f, self.object, self.stop_outer) }
+    }
+}
+
+// Recurses rather than collecting the outers, so there is no limit on how many
+// outers an object can have.
+unsafe fn write_path(f: &mut Formatter, object: *const UObject, stop_outer: *const UObject) -> Result<(), fmt::Error> {
+    let outer = (*object).OuterPrivate;
+
+    if !outer.is_null() && !ptr::eq(outer, stop_outer) {
+        write_path(f, outer, stop_outer)?;
+        f.write_char((*outer).subobject_delimiter())?;
+    }
+
+    // FName's Display writes the number as a "_N" suffix.
+    (*object).NamePrivate.fmt(f)
+}
+
+// "/Script/Package": the name of the package the object is in.
+#[derive(Copy, Clone)]
+pub struct PackageNameOf(pub *const UObject);
+
+impl Display for PackageNameOf {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        unsafe { (*(*self.0).package().cast::<UObject>()).NamePrivate.fmt(f) }
+    }
+}
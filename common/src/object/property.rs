@@ -0,0 +1,593 @@
+use crate::{EClassCastFlags, FField, FName, FString, FStringBuf, UClass, UEnum, UObject, UStruct, PROFILE};
+
+use core::mem;
+use core::ptr;
+
+#[repr(C)]
+pub struct FProperty {
+    pub base: FField,
+    pub ArrayDim: i32,
+    pub ElementSize: i32,
+    pub PropertyFlags: EPropertyFlags,
+    pad0: [u8; 4],
+    pub Offset: i32,
+    pad1: [u8; PROFILE.fproperty_trailing_size],
+}
+
+impl FProperty {
+    pub unsafe fn is(&self, property: EClassCastFlags) -> bool {
+        (*self.base.ClassPrivate).CastFlags.any(property)
+    }
+
+    pub unsafe fn id(&self) -> EClassCastFlags {
+        (*self.base.ClassPrivate).Id
+    }
+}
+
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct EPropertyFlags(pub u64);
+
+impl EPropertyFlags {
+    // Engine\Source\Runtime\CoreUObject\Public\UObject\ObjectMacros.h
+    pub const CPF_None: Self = Self(0);
+    pub const CPF_Edit: Self = Self(0x1); // < Property is user-settable in the editor.
+    pub const CPF_ConstParm: Self = Self(0x2); // < This is a constant function parameter
+    pub const CPF_BlueprintVisible: Self = Self(0x4); // < This property can be read by blueprint code
+    pub const CPF_ExportObject: Self = Self(0x8); // < Object can be exported with actor.
+    pub const CPF_BlueprintReadOnly: Self = Self(0x10); // < This property cannot be modified by blueprint code
+    pub const CPF_Net: Self = Self(0x20); // < Property is relevant to network replication.
+    pub const CPF_EditFixedSize: Self = Self(0x40); // < Indicates that elements of an array can be modified, but its size cannot be changed.
+    pub const CPF_Parm: Self = Self(0x80); // < Function/When call parameter.
+    pub const CPF_OutParm: Self = Self(0x100); // < Value is copied out after function call.
+    pub const CPF_ZeroConstructor: Self = Self(0x200); // < memset is fine for construction
+    pub const CPF_ReturnParm: Self = Self(0x400); // < Return value.
+    pub const CPF_DisableEditOnTemplate: Self = Self(0x800); // < Disable editing of this property on an archetype/sub-blueprint
+    pub const CPF_Transient: Self = Self(0x2000); // < Property is transient: shouldn't be saved or loaded, except for Blueprint CDOs.
+    pub const CPF_Config: Self = Self(0x4000); // < Property should be loaded/saved as permanent profile.
+    pub const CPF_DisableEditOnInstance: Self = Self(0x10000); // < Disable editing on an instance of this class
+    pub const CPF_EditConst: Self = Self(0x20000); // < Property is uneditable in the editor.
+    pub const CPF_GlobalConfig: Self = Self(0x40000); // < Load config from base class, not subclass.
+    pub const CPF_InstancedReference: Self = Self(0x80000); // < Property is a component references.
+    pub const CPF_DuplicateTransient: Self = Self(0x200000); // < Property should always be reset to the default value during any type of duplication (copy/paste, binary duplication, etc.)
+    pub const CPF_SubobjectReference: Self = Self(0x400000); // < Property contains subobject references (TSubobjectPtr)
+    pub const CPF_SaveGame: Self = Self(0x1000000); // < Property should be serialized for save games, this is only checked for game-specific archives with ArIsSaveGame
+    pub const CPF_NoClear: Self = Self(0x2000000); // < Hide clear (and browse) button.
+    pub const CPF_ReferenceParm: Self = Self(0x8000000); // < Value is passed by reference; CPF_OutParam and CPF_Param should also be set.
+    pub const CPF_BlueprintAssignable: Self = Self(0x10000000); // < MC Delegates only.  Property should be exposed for assigning in blueprint code
+    pub const CPF_Deprecated: Self = Self(0x20000000); // < Property is deprecated.  Read it from an archive, but don't save it.
+    pub const CPF_IsPlainOldData: Self = Self(0x40000000); // < If this is set, then the property can be memcopied instead of CopyCompleteValue / CopySingleValue
+    pub const CPF_RepSkip: Self = Self(0x80000000); // < Not replicated. For non replicated properties in replicated structs
+    pub const CPF_RepNotify: Self = Self(0x100000000); // < Notify actors when a property is replicated
+    pub const CPF_Interp: Self = Self(0x200000000); // < interpolatable property for use with matinee
+    pub const CPF_NonTransactional: Self = Self(0x400000000); // < Property isn't transacted
+    pub const CPF_EditorOnly: Self = Self(0x800000000); // < Property should only be loaded in the editor
+    pub const CPF_NoDestructor: Self = Self(0x1000000000); // < No destructor
+    pub const CPF_AutoWeak: Self = Self(0x4000000000); // < Only used for weak pointers, means the export type is autoweak
+    pub const CPF_ContainsInstancedReference: Self = Self(0x8000000000); // < Property contains component references.
+    pub const CPF_AssetRegistrySearchable: Self = Self(0x10000000000); // < asset instances will add properties with this flag to the asset registry automatically
+    pub const CPF_SimpleDisplay: Self = Self(0x20000000000); // < The property is visible by default in the editor details view
+    pub const CPF_AdvancedDisplay: Self = Self(0x40000000000); // < The property is advanced and not visible by default in the editor details view
+    pub const CPF_Protected: Self = Self(0x80000000000); // < property is protected from the perspective of script
+    pub const CPF_BlueprintCallable: Self = Self(0x100000000000); // < MC Delegates only.  Property should be exposed for calling in blueprint code
+    pub const CPF_BlueprintAuthorityOnly: Self = Self(0x200000000000); // < MC Delegates only.  This delegate accepts (only in blueprint) only events with BlueprintAuthorityOnly.
+    pub const CPF_TextExportTransient: Self = Self(0x400000000000); // < Property shouldn't be exported to text format (e.g. copy/paste)
+    pub const CPF_NonPIEDuplicateTransient: Self = Self(0x800000000000); // < Property should only be copied in PIE
+    pub const CPF_ExposeOnSpawn: Self = Self(0x1000000000000); // < Property is exposed on spawn
+    pub const CPF_PersistentInstance: Self = Self(0x2000000000000); // < A object referenced by the property is duplicated like a component. (Each actor should have an own instance.)
+    pub const CPF_UObjectWrapper: Self = Self(0x4000000000000); // < Property was parsed as a wrapper class like TSubclassOf<T>, FScriptInterface etc., rather than a USomething*
+    pub const CPF_HasGetValueTypeHash: Self = Self(0x8000000000000); // < This property can generate a meaningful hash value.
+    pub const CPF_NativeAccessSpecifierPublic: Self = Self(0x10000000000000); // < Public native access specifier
+    pub const CPF_NativeAccessSpecifierProtected: Self = Self(0x20000000000000); // < Protected native access specifier
+    pub const CPF_NativeAccessSpecifierPrivate: Self = Self(0x40000000000000); // < Private native access specifier
+    pub const CPF_SkipSerialization: Self = Self(0x80000000000000); // < Property shouldn't be serialized, can still be exported to text
+
+    pub fn contains(&self, flag: Self) -> bool {
+        self.0 & flag.0 == flag.0
+    }
+}
+
+#[repr(C)]
+pub struct FBoolProperty {
+    pub base: FProperty,
+    pub FieldSize: u8,
+    pub ByteOffset: u8,
+    pub ByteMask: u8,
+    pub FieldMask: u8,
+    pad: [u8; 4],
+}
+
+impl FBoolProperty {
+    pub fn is_bitfield(&self) -> bool {
+        self.FieldMask != 255
+    }
+}
+
+#[repr(C)]
+pub struct FByteProperty {
+    pub base: FProperty,
+    pub Enumeration: *const UEnum,
+}
+
+#[repr(C)]
+pub struct FStructProperty {
+    pub base: FProperty,
+    pub Structure: *const UStruct,
+}
+
+#[repr(C)]
+pub struct FObjectPropertyBase {
+    pub base: FProperty,
+    pub PropertyClass: *const UClass,
+}
+
+#[repr(C)]
+pub struct FClassProperty {
+    pub base: FObjectPropertyBase,
+    pub MetaClass: *const UClass,
+}
+
+#[repr(C)]
+pub struct FArrayProperty {
+    pub base: FProperty,
+    pub Inner: *const FProperty,
+    pad: [u8; PROFILE.farray_property_trailing_size],
+}
+
+#[repr(C)]
+pub struct FEnumProperty {
+    pub base: FProperty,
+    pub UnderlyingProp: *const FProperty,
+    pub Enumeration: *const UEnum,
+}
+
+#[repr(C)]
+pub struct FInterfaceProperty {
+    pub base: FProperty,
+    pub InterfaceClass: *const UClass,
+}
+
+#[repr(C)]
+pub struct FMapProperty {
+    pub base: FProperty,
+    pub KeyProp: *const FProperty,
+    pub ValueProp: *const FProperty,
+    pad: [u8; PROFILE.fmap_property_layout_size],
+}
+
+#[repr(C)]
+pub struct FSetProperty {
+    pub base: FProperty,
+    pub ElementProp: *const FProperty,
+    pad: [u8; PROFILE.fset_property_layout_size],
+}
+
+#[repr(C)]
+pub struct FSoftClassProperty {
+    pub base: FObjectPropertyBase,
+    pub MetaClass: *const UClass,
+}
+
+// #[repr(C)]
+// pub struct FFieldPathProperty {
+//     pub base: FProperty,
+//     PropertyClass: *const FFieldClass,
+// }
+
+// A property of a struct, class or function.
+//
+// Unlike the raw `FProperty` layouts above, this only hands out what the
+// reflection data says, so code outside the SDK generator can inspect objects
+// without knowing their types up front.
+#[derive(Copy, Clone)]
+pub struct Property<'a> {
+    raw: &'a FProperty,
+}
+
+impl<'a> Property<'a> {
+    pub unsafe fn new(property: *const FProperty) -> Option<Property<'a>> {
+        property.as_ref().map(|raw| Property { raw })
+    }
+
+    pub fn as_ptr(&self) -> *const FProperty {
+        self.raw
+    }
+
+    pub fn name(&self) -> &'a str {
+        unsafe { self.raw.base.name() }
+    }
+
+    pub fn fname(&self) -> FName {
+        self.raw.base.NamePrivate
+    }
+
+    // Byte offset of the property within its owning struct.
+    pub fn offset(&self) -> usize {
+        self.raw.Offset as usize
+    }
+
+    // Size in bytes of the whole property, i.e. all of its static array
+    // elements.
+    pub fn size(&self) -> usize {
+        self.element_size() * self.array_dim()
+    }
+
+    pub fn element_size(&self) -> usize {
+        self.raw.ElementSize as usize
+    }
+
+    // Number of elements in a C-style static array, or 1 if the property isn't
+    // one.
+    pub fn array_dim(&self) -> usize {
+        self.raw.ArrayDim as usize
+    }
+
+    pub fn flags(&self) -> EPropertyFlags {
+        self.raw.PropertyFlags
+    }
+
+    pub fn kind(&self) -> PropertyKind<'a> {
+        unsafe { PropertyKind::of(self.raw) }
+    }
+
+    // Frees the strings in the value at `value` and zeroes them, like the
+    // engine's `DestroyValue` for the types that can be written through
+    // `PropertyValue`. Other values that own memory, like arrays, are left
+    // alone.
+    pub unsafe fn destroy_value(&self, value: *mut u8) {
+        if let PropertyKind::Str = self.kind() {
+            for i in 0..self.array_dim() {
+                let string = value.add(i * self.element_size()).cast::<FString>();
+                let mut owned = string.read_unaligned();
+
+                if owned.free().is_ok() {
+                    string.write_unaligned(owned);
+                }
+            }
+        }
+    }
+}
+
+// What a `Property` holds. Types a property refers to, such as an object
+// property's class, are `None` when the engine left them unset.
+#[derive(Copy, Clone)]
+pub enum PropertyKind<'a> {
+    Bool {
+        // The byte holding the value, relative to the property's offset.
+        byte_offset: u8,
+        // The bit that is set for `true`.
+        byte_mask: u8,
+        // The bits that make up the value: just `byte_mask` in a bitfield, or
+        // 0xFF for a plain `bool`.
+        field_mask: u8,
+    },
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Byte(Option<&'a UEnum>),
+    UInt16,
+    UInt32,
+    UInt64,
+    Float,
+    Double,
+    Enum {
+        underlying: Option<Property<'a>>,
+        enumeration: Option<&'a UEnum>,
+    },
+    Name,
+    Str,
+    Text,
+    Object(Option<&'a UClass>),
+    WeakObject(Option<&'a UClass>),
+    LazyObject(Option<&'a UClass>),
+    SoftObject(Option<&'a UClass>),
+    Class(Option<&'a UClass>),
+    SoftClass(Option<&'a UClass>),
+    Interface(Option<&'a UClass>),
+    Struct(Option<&'a UStruct>),
+    Array(Property<'a>),
+    Map {
+        key: Property<'a>,
+        value: Property<'a>,
+    },
+    Set(Property<'a>),
+    Delegate,
+    MulticastInlineDelegate,
+    MulticastSparseDelegate,
+    FieldPath,
+    // A property type this API doesn't model, or a container whose inner
+    // property is missing.
+    Unknown(EClassCastFlags),
+}
+
+impl<'a> PropertyKind<'a> {
+    unsafe fn of(property: &'a FProperty) -> PropertyKind<'a> {
+        let raw: *const FProperty = property;
+        let id = property.id();
+
+        match id {
+            EClassCastFlags::CASTCLASS_FBoolProperty => {
+                let property = &*raw.cast::<FBoolProperty>();
+
+                Self::Bool {
+                    byte_offset: property.ByteOffset,
+                    byte_mask: property.ByteMask,
+                    field_mask: property.FieldMask,
+                }
+            }
+
+            EClassCastFlags::CASTCLASS_FInt8Property => Self::Int8,
+            EClassCastFlags::CASTCLASS_FInt16Property => Self::Int16,
+            EClassCastFlags::CASTCLASS_FIntProperty => Self::Int32,
+            EClassCastFlags::CASTCLASS_FInt64Property => Self::Int64,
+            EClassCastFlags::CASTCLASS_FUInt16Property => Self::UInt16,
+            EClassCastFlags::CASTCLASS_FUInt32Property => Self::UInt32,
+            EClassCastFlags::CASTCLASS_FUInt64Property => Self::UInt64,
+            EClassCastFlags::CASTCLASS_FFloatProperty => Self::Float,
+            EClassCastFlags::CASTCLASS_FDoubleProperty => Self::Double,
+
+            EClassCastFlags::CASTCLASS_FByteProperty => {
+                Self::Byte((*raw.cast::<FByteProperty>()).Enumeration.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FEnumProperty => {
+                let property = &*raw.cast::<FEnumProperty>();
+
+                Self::Enum {
+                    underlying: Property::new(property.UnderlyingProp),
+                    enumeration: property.Enumeration.as_ref(),
+                }
+            }
+
+            EClassCastFlags::CASTCLASS_FNameProperty => Self::Name,
+            EClassCastFlags::CASTCLASS_FStrProperty => Self::Str,
+            EClassCastFlags::CASTCLASS_FTextProperty => Self::Text,
+
+            EClassCastFlags::CASTCLASS_FObjectProperty => {
+                Self::Object((*raw.cast::<FObjectPropertyBase>()).PropertyClass.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FWeakObjectProperty => {
+                Self::WeakObject((*raw.cast::<FObjectPropertyBase>()).PropertyClass.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FLazyObjectProperty => {
+                Self::LazyObject((*raw.cast::<FObjectPropertyBase>()).PropertyClass.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FSoftObjectProperty => {
+                Self::SoftObject((*raw.cast::<FObjectPropertyBase>()).PropertyClass.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FClassProperty => {
+                Self::Class((*raw.cast::<FClassProperty>()).MetaClass.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FSoftClassProperty => {
+                Self::SoftClass((*raw.cast::<FSoftClassProperty>()).MetaClass.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FInterfaceProperty => {
+                Self::Interface((*raw.cast::<FInterfaceProperty>()).InterfaceClass.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FStructProperty => {
+                Self::Struct((*raw.cast::<FStructProperty>()).Structure.as_ref())
+            }
+
+            EClassCastFlags::CASTCLASS_FArrayProperty => {
+                match Property::new((*raw.cast::<FArrayProperty>()).Inner) {
+                    Some(inner) => Self::Array(inner),
+                    None => Self::Unknown(id),
+                }
+            }
+
+            EClassCastFlags::CASTCLASS_FMapProperty => {
+                let map = &*raw.cast::<FMapProperty>();
+
+                match (Property::new(map.KeyProp), Property::new(map.ValueProp)) {
+                    (Some(key), Some(value)) => Self::Map { key, value },
+                    _ => Self::Unknown(id),
+                }
+            }
+
+            EClassCastFlags::CASTCLASS_FSetProperty => {
+                match Property::new((*raw.cast::<FSetProperty>()).ElementProp) {
+                    Some(element) => Self::Set(element),
+                    None => Self::Unknown(id),
+                }
+            }
+
+            EClassCastFlags::CASTCLASS_FDelegateProperty => Self::Delegate,
+            EClassCastFlags::CASTCLASS_FMulticastInlineDelegateProperty => Self::MulticastInlineDelegate,
+            EClassCastFlags::CASTCLASS_FMulticastSparseDelegateProperty => Self::MulticastSparseDelegate,
+            EClassCastFlags::CASTCLASS_FFieldPathProperty => Self::FieldPath,
+
+            _ => Self::Unknown(id),
+        }
+    }
+}
+
+// Iterates the properties of a struct and, unless only its own were asked for,
+// then those of each of its super structs, so a class's inherited fields come
+// after its own.
+pub struct Properties<'a> {
+    structure: Option<&'a UStruct>,
+    field: *const FField,
+    include_supers: bool,
+}
+
+impl<'a> Properties<'a> {
+    pub(crate) fn new(structure: &'a UStruct, include_supers: bool) -> Properties<'a> {
+        Properties {
+            structure: Some(structure),
+            field: ptr::null(),
+            include_supers,
+        }
+    }
+}
+
+impl<'a> Iterator for Properties<'a> {
+    type Item = Property<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            loop {
+                if let Some(field) = self.field.as_ref() {
+                    self.field = field.Next;
+
+                    // Struct children are all properties today, but the
+                    // field list is typed as `FField`, so check anyway.
+                    if (*field.ClassPrivate).CastFlags.any(EClassCastFlags::CASTCLASS_FProperty) {
+                        return Property::new((field as *const FField).cast());
+                    }
+                } else {
+                    let structure = self.structure?;
+                    self.field = structure.ChildProperties;
+
+                    self.structure = if self.include_supers {
+                        (structure.SuperStruct as *const UStruct).as_ref()
+                    } else {
+                        None
+                    };
+                }
+            }
+        }
+    }
+}
+
+// A Rust type that `UObject::get` and `UObject::set` can move in and out of a
+// property.
+//
+// Unsafe to implement because `read` and `write` trust `matches` to have
+// checked that the property holds a `Self`.
+pub unsafe trait PropertyValue: Sized {
+    fn matches(property: Property) -> bool;
+
+    // Whether the property can hold this value in particular, once `matches`
+    // has checked its type. Setters fail with `PropertyTypeMismatch` if not.
+    unsafe fn accepts(&self, _: Property) -> bool {
+        true
+    }
+
+    // `value` points to the property's value itself, wherever it lives.
+    unsafe fn read_value(value: *const u8, _: Property) -> Self {
+        value.cast::<Self>().read_unaligned()
+    }
+
+    unsafe fn write_value(self, value: *mut u8, _: Property) {
+        value.cast::<Self>().write_unaligned(self);
+    }
+
+    // `data` points to the start of the object or struct that owns `property`.
+    unsafe fn read(data: *const u8, property: Property) -> Self {
+        Self::read_value(data.add(property.offset()), property)
+    }
+
+    unsafe fn write(self, data: *mut u8, property: Property) {
+        self.write_value(data.add(property.offset()), property);
+    }
+}
+
+macro_rules! impl_property_value {
+    ($($Type:ty => $Kind:pat,)*) => {
+        $(
+            unsafe impl PropertyValue for $Type {
+                fn matches(property: Property) -> bool {
+                    matches!(property.kind(), $Kind) && property.size() == mem::size_of::<Self>()
+                }
+            }
+        )*
+    };
+}
+
+impl_property_value! {
+    i8 => PropertyKind::Int8,
+    i16 => PropertyKind::Int16,
+    i32 => PropertyKind::Int32,
+    i64 => PropertyKind::Int64,
+    u8 => PropertyKind::Byte(_) | PropertyKind::Enum { .. },
+    u16 => PropertyKind::UInt16,
+    u32 => PropertyKind::UInt32,
+    u64 => PropertyKind::UInt64,
+    f32 => PropertyKind::Float,
+    f64 => PropertyKind::Double,
+    FName => PropertyKind::Name,
+}
+
+// A string property owns its buffer, which the engine frees along with the
+// property's owner. So strings are copied both ways: reading copies as much as
+// fits into the `FStringBuf`, and writing stores a copy from the engine's
+// allocator in place of the old string, which is freed. Writing leaves the
+// property unchanged if the allocator wasn't found or is out of memory.
+unsafe impl<const N: usize> PropertyValue for FStringBuf<N> {
+    fn matches(property: Property) -> bool {
+        matches!(property.kind(), PropertyKind::Str) && property.size() == mem::size_of::<FString>()
+    }
+
+    unsafe fn read_value(value: *const u8, _: Property) -> Self {
+        FStringBuf::truncated(value.cast::<FString>().read_unaligned().as_slice())
+    }
+
+    unsafe fn write_value(self, value: *mut u8, _: Property) {
+        let value = value.cast::<FString>();
+
+        if let Ok(copy) = FString::allocate_units(self.as_slice().iter().copied()) {
+            let _ = value.read_unaligned().free();
+            value.write_unaligned(copy);
+        }
+    }
+}
+
+unsafe impl PropertyValue for *mut UObject {
+    fn matches(property: Property) -> bool {
+        matches!(property.kind(), PropertyKind::Object(_) | PropertyKind::Class(_))
+            && property.size() == mem::size_of::<Self>()
+    }
+
+    // Besides null, an object property takes an object of its class, and a
+    // class property a class derived from its meta class.
+    unsafe fn accepts(&self, property: Property) -> bool {
+        let object = match self.as_ref() {
+            Some(object) => object,
+            None => return true,
+        };
+
+        match property.kind() {
+            PropertyKind::Object(Some(class)) => object.is(class),
+
+            PropertyKind::Class(Some(meta_class)) => {
+                object.fast_is(EClassCastFlags::CASTCLASS_UClass) && {
+                    let class = &*self.cast::<UClass>();
+                    class.is(&**meta_class)
+                }
+            }
+
+            _ => true,
+        }
+    }
+}
+
+// A `bool` property may be one bit of a bitfield, so it's read and written
+// through its masks rather than as a whole byte.
+unsafe impl PropertyValue for bool {
+    fn matches(property: Property) -> bool {
+        matches!(property.kind(), PropertyKind::Bool { .. }) && property.array_dim() == 1
+    }
+
+    unsafe fn read_value(value: *const u8, property: Property) -> Self {
+        if let PropertyKind::Bool { byte_offset, field_mask, .. } = property.kind() {
+            *value.add(usize::from(byte_offset)) & field_mask != 0
+        } else {
+            false
+        }
+    }
+
+    unsafe fn write_value(self, value: *mut u8, property: Property) {
+        if let PropertyKind::Bool { byte_offset, byte_mask, field_mask } = property.kind() {
+            let byte = value.add(usize::from(byte_offset));
+            *byte = (*byte & !field_mask) | if self { byte_mask } else { 0 };
+        }
+    }
+}
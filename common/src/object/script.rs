@@ -0,0 +1,785 @@
+use super::{FField, PathNameOf, UObject};
+use crate::FScriptName;
+
+use core::char;
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter};
+
+// A disassembler for Kismet bytecode, the script that blueprint functions run.
+//
+// The bytecode is a tree of expressions. Each one starts with an `EExprToken`
+// byte, followed by its operands, some of which are nested expressions. This
+// follows the in-memory layout of a loaded 64-bit game, where object and
+// property references are raw pointers rather than the package indices of
+// the serialized form.
+
+macro_rules! tokens {
+    ($($name:ident = $value:literal,)*) => {
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        #[repr(transparent)]
+        pub struct EExprToken(pub u8);
+
+        impl EExprToken {
+            $(pub const $name: Self = Self($value);)*
+
+            pub fn name(&self) -> Option<&'static str> {
+                match self.0 {
+                    $($value => Some(stringify!($name)),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+tokens! {
+    EX_LocalVariable = 0x00,
+    EX_InstanceVariable = 0x01,
+    EX_DefaultVariable = 0x02,
+    EX_Return = 0x04,
+    EX_Jump = 0x06,
+    EX_JumpIfNot = 0x07,
+    EX_Assert = 0x09,
+    EX_Nothing = 0x0B,
+    EX_Let = 0x0F,
+    EX_ClassContext = 0x12,
+    EX_MetaCast = 0x13,
+    EX_LetBool = 0x14,
+    EX_EndParmValue = 0x15,
+    EX_EndFunctionParms = 0x16,
+    EX_Self = 0x17,
+    EX_Skip = 0x18,
+    EX_Context = 0x19,
+    EX_Context_FailSilent = 0x1A,
+    EX_VirtualFunction = 0x1B,
+    EX_FinalFunction = 0x1C,
+    EX_IntConst = 0x1D,
+    EX_FloatConst = 0x1E,
+    EX_StringConst = 0x1F,
+    EX_ObjectConst = 0x20,
+    EX_NameConst = 0x21,
+    EX_RotationConst = 0x22,
+    EX_VectorConst = 0x23,
+    EX_ByteConst = 0x24,
+    EX_IntZero = 0x25,
+    EX_IntOne = 0x26,
+    EX_True = 0x27,
+    EX_False = 0x28,
+    EX_TextConst = 0x29,
+    EX_NoObject = 0x2A,
+    EX_TransformConst = 0x2B,
+    EX_IntConstByte = 0x2C,
+    EX_NoInterface = 0x2D,
+    EX_DynamicCast = 0x2E,
+    EX_StructConst = 0x2F,
+    EX_EndStructConst = 0x30,
+    EX_SetArray = 0x31,
+    EX_EndArray = 0x32,
+    EX_PropertyConst = 0x33,
+    EX_UnicodeStringConst = 0x34,
+    EX_Int64Const = 0x35,
+    EX_UInt64Const = 0x36,
+    EX_PrimitiveCast = 0x38,
+    EX_SetSet = 0x39,
+    EX_EndSet = 0x3A,
+    EX_SetMap = 0x3B,
+    EX_EndMap = 0x3C,
+    EX_SetConst = 0x3D,
+    EX_EndSetConst = 0x3E,
+    EX_MapConst = 0x3F,
+    EX_EndMapConst = 0x40,
+    EX_StructMemberContext = 0x42,
+    EX_LetMulticastDelegate = 0x43,
+    EX_LetDelegate = 0x44,
+    EX_LocalVirtualFunction = 0x45,
+    EX_LocalFinalFunction = 0x46,
+    EX_LocalOutVariable = 0x48,
+    EX_DeprecatedOp4A = 0x4A,
+    EX_InstanceDelegate = 0x4B,
+    EX_PushExecutionFlow = 0x4C,
+    EX_PopExecutionFlow = 0x4D,
+    EX_ComputedJump = 0x4E,
+    EX_PopExecutionFlowIfNot = 0x4F,
+    EX_Breakpoint = 0x50,
+    EX_InterfaceContext = 0x51,
+    EX_ObjToInterfaceCast = 0x52,
+    EX_EndOfScript = 0x53,
+    EX_CrossInterfaceCast = 0x54,
+    EX_InterfaceToObjCast = 0x55,
+    EX_WireTracepoint = 0x5A,
+    EX_SkipOffsetConst = 0x5B,
+    EX_AddMulticastDelegate = 0x5C,
+    EX_ClearMulticastDelegate = 0x5D,
+    EX_Tracepoint = 0x5E,
+    EX_LetObj = 0x5F,
+    EX_LetWeakObjPtr = 0x60,
+    EX_BindDelegate = 0x61,
+    EX_RemoveMulticastDelegate = 0x62,
+    EX_CallMulticastDelegate = 0x63,
+    EX_LetValueOnPersistentFrame = 0x64,
+    EX_ArrayConst = 0x65,
+    EX_EndArrayConst = 0x66,
+    EX_SoftObjectConst = 0x67,
+    EX_CallMath = 0x68,
+    EX_SwitchValue = 0x69,
+    EX_InstrumentationEvent = 0x6A,
+    EX_ArrayGetByRef = 0x6B,
+    EX_ClassSparseDataVariable = 0x6C,
+    EX_FieldPathConst = 0x6D,
+}
+
+impl Display for EExprToken {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "EX_Unknown({:#04x})", self.0),
+        }
+    }
+}
+
+// EBlueprintTextLiteralType
+const TEXT_EMPTY: u8 = 0;
+const TEXT_LOCALIZED: u8 = 1;
+const TEXT_INVARIANT: u8 = 2;
+const TEXT_LITERAL_STRING: u8 = 3;
+const TEXT_STRING_TABLE_ENTRY: u8 = 4;
+
+// EScriptInstrumentation::InlineEvent, the only event that carries a name.
+const INSTRUMENTATION_INLINE_EVENT: u8 = 4;
+
+// How deeply expressions may nest before the bytecode counts as malformed.
+// Compiled blueprints stay far shallower, and the walker recurses once per
+// level, so this keeps garbage from overflowing the stack.
+const MAX_DEPTH: usize = 64;
+
+// Names the objects, properties and names that bytecode refers to. The
+// defaults write the raw values, which is all that script copied out of
+// another process, like the bytes of an offline dump, has to offer.
+pub trait ScriptReferences {
+    // `address` points to an `FField`.
+    fn write_property(&self, f: &mut Formatter, address: u64) -> Result<(), fmt::Error> {
+        write!(f, "{:#x}", address)
+    }
+
+    // `address` points to a `UObject`.
+    fn write_object(&self, f: &mut Formatter, address: u64) -> Result<(), fmt::Error> {
+        write!(f, "{:#x}", address)
+    }
+
+    fn write_name(&self, f: &mut Formatter, name: FScriptName) -> Result<(), fmt::Error> {
+        write!(f, "FName({}, {})", name.ComparisonIndex, name.Number)
+    }
+}
+
+pub struct RawReferences;
+
+impl ScriptReferences for RawReferences {}
+
+// Resolves references through this process, for the script of a live
+// `UStruct`.
+pub struct LiveReferences(());
+
+impl LiveReferences {
+    // The bytecode must come from a struct that is loaded in this process, so
+    // that its references point to live objects and properties.
+    pub unsafe fn new() -> LiveReferences {
+        LiveReferences(())
+    }
+}
+
+impl ScriptReferences for LiveReferences {
+    fn write_property(&self, f: &mut Formatter, address: u64) -> Result<(), fmt::Error> {
+        let property = address as *const FField;
+
+        if property.is_null() {
+            f.write_str("null")
+        } else {
+            unsafe { f.write_str((*property).name()) }
+        }
+    }
+
+    fn write_object(&self, f: &mut Formatter, address: u64) -> Result<(), fmt::Error> {
+        let object = address as *const UObject;
+
+        if object.is_null() {
+            f.write_str("null")
+        } else {
+            PathNameOf::new(object).fmt(f)
+        }
+    }
+
+    fn write_name(&self, f: &mut Formatter, name: FScriptName) -> Result<(), fmt::Error> {
+        name.to_fname().fmt(f)
+    }
+}
+
+// Displays bytecode as one expression per line, each with its offset and
+// indented under the expression it belongs to:
+//
+//     000000 EX_Let bHasTarget
+//     000009   EX_LocalVariable bHasTarget
+//     000012   EX_True
+//
+// Malformed bytecode ends the listing at the point it stops making sense.
+pub struct Disassembly<'code, R> {
+    code: &'code [u8],
+    references: R,
+}
+
+impl<'code, R: ScriptReferences> Disassembly<'code, R> {
+    pub fn new(code: &'code [u8], references: R) -> Disassembly<'code, R> {
+        Disassembly { code, references }
+    }
+}
+
+impl<'code, R: ScriptReferences> Display for Disassembly<'code, R> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let mut walker = Walker {
+            f,
+            references: &self.references,
+            code: self.code,
+            offset: 0,
+            is_first_line: true,
+        };
+
+        while walker.offset < self.code.len() {
+            match walker.expr(0) {
+                Ok(_) => {}
+                Err(Stop::Fmt(e)) => return Err(e),
+                Err(Stop::Truncated) => return walker.f.write_str("\n<truncated>"),
+                Err(Stop::Malformed) => return walker.f.write_str("\n<can't decode further>"),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+enum Stop {
+    Fmt(fmt::Error),
+    Truncated,
+    Malformed,
+}
+
+impl From<fmt::Error> for Stop {
+    fn from(e: fmt::Error) -> Self {
+        Stop::Fmt(e)
+    }
+}
+
+type Step<T> = Result<T, Stop>;
+
+struct Walker<'f, 'w, 'code, R> {
+    f: &'f mut Formatter<'w>,
+    references: &'f R,
+    code: &'code [u8],
+    offset: usize,
+    is_first_line: bool,
+}
+
+impl<'f, 'w, 'code, R: ScriptReferences> Walker<'f, 'w, 'code, R> {
+    fn bytes<const N: usize>(&mut self) -> Step<[u8; N]> {
+        let bytes = self
+            .code
+            .get(self.offset..self.offset + N)
+            .and_then(|bytes| <[u8; N]>::try_from(bytes).ok())
+            .ok_or(Stop::Truncated)?;
+
+        self.offset += N;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Step<u8> {
+        self.bytes().map(u8::from_le_bytes)
+    }
+
+    fn u16(&mut self) -> Step<u16> {
+        self.bytes().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Step<u32> {
+        self.bytes().map(u32::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Step<i32> {
+        self.bytes().map(i32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Step<u64> {
+        self.bytes().map(u64::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Step<i64> {
+        self.bytes().map(i64::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Step<f32> {
+        self.bytes().map(f32::from_le_bytes)
+    }
+
+    // Starts a line for what begins at `offset`, `depth` levels in.
+    fn line(&mut self, offset: usize, depth: usize) -> Step<()> {
+        if !self.is_first_line {
+            self.f.write_str("\n")?;
+        }
+
+        self.is_first_line = false;
+        write!(self.f, "{:06x} {:indent$}", offset, "", indent = 2 * depth)?;
+        Ok(())
+    }
+
+    fn property(&mut self) -> Step<()> {
+        let address = self.u64()?;
+        self.f.write_str(" ")?;
+        self.references.write_property(self.f, address)?;
+        Ok(())
+    }
+
+    fn object(&mut self) -> Step<()> {
+        let address = self.u64()?;
+        self.f.write_str(" ")?;
+        self.references.write_object(self.f, address)?;
+        Ok(())
+    }
+
+    fn name(&mut self) -> Step<()> {
+        let name = FScriptName {
+            ComparisonIndex: self.u32()?,
+            DisplayIndex: self.u32()?,
+            Number: self.u32()?,
+        };
+
+        self.f.write_str(" ")?;
+        self.references.write_name(self.f, name)?;
+        Ok(())
+    }
+
+    // An offset into the bytecode, as a jump target.
+    fn code_offset(&mut self) -> Step<()> {
+        let offset = self.u32()?;
+        write!(self.f, " -> {:06x}", offset)?;
+        Ok(())
+    }
+
+    fn count(&mut self) -> Step<()> {
+        let count = self.i32()?;
+        write!(self.f, " ({} elements)", count)?;
+        Ok(())
+    }
+
+    fn floats<const N: usize>(&mut self) -> Step<()> {
+        self.f.write_str(" (")?;
+
+        for i in 0..N {
+            if i > 0 {
+                self.f.write_str(", ")?;
+            }
+
+            let float = self.f32()?;
+            write!(self.f, "{}", float)?;
+        }
+
+        self.f.write_str(")")?;
+        Ok(())
+    }
+
+    fn ansi_string(&mut self) -> Step<()> {
+        self.f.write_str(" \"")?;
+
+        loop {
+            match self.u8()? {
+                0 => break,
+                c => write!(self.f, "{}", char::from(c).escape_debug())?,
+            }
+        }
+
+        self.f.write_str("\"")?;
+        Ok(())
+    }
+
+    fn wide_string(&mut self) -> Step<()> {
+        let start = self.offset;
+
+        while self.u16()? != 0 {}
+
+        let units = self
+            .code
+            .get(start..self.offset - 2)
+            .unwrap_or_default()
+            .chunks_exact(2)
+            .map(|unit| <[u8; 2]>::try_from(unit).map_or(0, u16::from_le_bytes));
+
+        self.f.write_str(" \"")?;
+
+        for c in char::decode_utf16(units) {
+            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+            write!(self.f, "{}", c.escape_debug())?;
+        }
+
+        self.f.write_str("\"")?;
+        Ok(())
+    }
+
+    // Nested expressions up to and including `end`.
+    fn exprs_until(&mut self, end: EExprToken, depth: usize) -> Step<()> {
+        while self.expr(depth)? != end {}
+        Ok(())
+    }
+
+    fn expr(&mut self, depth: usize) -> Step<EExprToken> {
+        if depth > MAX_DEPTH {
+            return Err(Stop::Malformed);
+        }
+
+        let offset = self.offset;
+        let token = EExprToken(self.u8()?);
+        let inner = depth + 1;
+
+        self.line(offset, depth)?;
+        write!(self.f, "{}", token)?;
+
+        match token {
+            EExprToken::EX_LocalVariable
+            | EExprToken::EX_InstanceVariable
+            | EExprToken::EX_DefaultVariable
+            | EExprToken::EX_LocalOutVariable
+            | EExprToken::EX_ClassSparseDataVariable
+            | EExprToken::EX_PropertyConst => self.property()?,
+
+            EExprToken::EX_Return
+            | EExprToken::EX_ComputedJump
+            | EExprToken::EX_PopExecutionFlowIfNot
+            | EExprToken::EX_InterfaceContext
+            | EExprToken::EX_SoftObjectConst
+            | EExprToken::EX_FieldPathConst
+            | EExprToken::EX_ClearMulticastDelegate => {
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_Jump | EExprToken::EX_PushExecutionFlow | EExprToken::EX_SkipOffsetConst => {
+                self.code_offset()?;
+            }
+
+            EExprToken::EX_JumpIfNot | EExprToken::EX_Skip => {
+                self.code_offset()?;
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_Assert => {
+                let line = self.u16()?;
+                let is_debug = self.u8()? != 0;
+                write!(self.f, " line {}, debug {}", line, is_debug)?;
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_Let => {
+                self.property()?;
+                self.expr(inner)?;
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_LetBool
+            | EExprToken::EX_LetObj
+            | EExprToken::EX_LetWeakObjPtr
+            | EExprToken::EX_LetDelegate
+            | EExprToken::EX_LetMulticastDelegate
+            | EExprToken::EX_AddMulticastDelegate
+            | EExprToken::EX_RemoveMulticastDelegate
+            | EExprToken::EX_ArrayGetByRef => {
+                self.expr(inner)?;
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_ClassContext | EExprToken::EX_Context | EExprToken::EX_Context_FailSilent => {
+                self.expr(inner)?;
+
+                // Where to skip to if the object is null, and the property to
+                // zero when it is.
+                self.line(self.offset, inner)?;
+                self.f.write_str("skip")?;
+                self.code_offset()?;
+                self.f.write_str(", r-value")?;
+                self.property()?;
+
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_MetaCast
+            | EExprToken::EX_DynamicCast
+            | EExprToken::EX_ObjToInterfaceCast
+            | EExprToken::EX_CrossInterfaceCast
+            | EExprToken::EX_InterfaceToObjCast => {
+                self.object()?;
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_VirtualFunction | EExprToken::EX_LocalVirtualFunction => {
+                self.name()?;
+                self.exprs_until(EExprToken::EX_EndFunctionParms, inner)?;
+            }
+
+            EExprToken::EX_FinalFunction | EExprToken::EX_LocalFinalFunction | EExprToken::EX_CallMath => {
+                self.object()?;
+                self.exprs_until(EExprToken::EX_EndFunctionParms, inner)?;
+            }
+
+            EExprToken::EX_CallMulticastDelegate => {
+                self.object()?;
+                self.expr(inner)?;
+                self.exprs_until(EExprToken::EX_EndFunctionParms, inner)?;
+            }
+
+            EExprToken::EX_IntConst => {
+                let value = self.i32()?;
+                write!(self.f, " {}", value)?;
+            }
+
+            EExprToken::EX_FloatConst => {
+                let value = self.f32()?;
+                write!(self.f, " {}", value)?;
+            }
+
+            EExprToken::EX_ByteConst | EExprToken::EX_IntConstByte => {
+                let value = self.u8()?;
+                write!(self.f, " {}", value)?;
+            }
+
+            EExprToken::EX_Int64Const => {
+                let value = self.i64()?;
+                write!(self.f, " {}", value)?;
+            }
+
+            EExprToken::EX_UInt64Const => {
+                let value = self.u64()?;
+                write!(self.f, " {}", value)?;
+            }
+
+            EExprToken::EX_StringConst => self.ansi_string()?,
+
+            EExprToken::EX_UnicodeStringConst => self.wide_string()?,
+
+            EExprToken::EX_ObjectConst => self.object()?,
+
+            EExprToken::EX_NameConst | EExprToken::EX_InstanceDelegate => self.name()?,
+
+            // Pitch, yaw and roll, or X, Y and Z.
+            EExprToken::EX_RotationConst | EExprToken::EX_VectorConst => self.floats::<3>()?,
+
+            EExprToken::EX_TransformConst => {
+                self.f.write_str(" rotation")?;
+                self.floats::<4>()?;
+                self.f.write_str(", translation")?;
+                self.floats::<3>()?;
+                self.f.write_str(", scale")?;
+                self.floats::<3>()?;
+            }
+
+            EExprToken::EX_TextConst => match self.u8()? {
+                TEXT_EMPTY => self.f.write_str(" empty")?,
+
+                TEXT_LOCALIZED => {
+                    // Source string, key and namespace.
+                    self.f.write_str(" localized")?;
+                    self.expr(inner)?;
+                    self.expr(inner)?;
+                    self.expr(inner)?;
+                }
+
+                TEXT_INVARIANT => {
+                    self.f.write_str(" invariant")?;
+                    self.expr(inner)?;
+                }
+
+                TEXT_LITERAL_STRING => {
+                    self.f.write_str(" literal")?;
+                    self.expr(inner)?;
+                }
+
+                TEXT_STRING_TABLE_ENTRY => {
+                    // The string table asset, then the table id and key.
+                    self.f.write_str(" string table")?;
+                    self.object()?;
+                    self.expr(inner)?;
+                    self.expr(inner)?;
+                }
+
+                _ => return Err(Stop::Malformed),
+            },
+
+            EExprToken::EX_StructConst => {
+                self.object()?;
+                let size = self.i32()?;
+                write!(self.f, " ({} bytes)", size)?;
+                self.exprs_until(EExprToken::EX_EndStructConst, inner)?;
+            }
+
+            EExprToken::EX_SetArray => {
+                self.expr(inner)?;
+                self.exprs_until(EExprToken::EX_EndArray, inner)?;
+            }
+
+            EExprToken::EX_PrimitiveCast => {
+                let conversion = self.u8()?;
+                write!(self.f, " conversion {}", conversion)?;
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_SetSet => {
+                self.expr(inner)?;
+                self.count()?;
+                self.exprs_until(EExprToken::EX_EndSet, inner)?;
+            }
+
+            EExprToken::EX_SetMap => {
+                self.expr(inner)?;
+                self.count()?;
+                self.exprs_until(EExprToken::EX_EndMap, inner)?;
+            }
+
+            EExprToken::EX_SetConst => {
+                self.property()?;
+                self.count()?;
+                self.exprs_until(EExprToken::EX_EndSetConst, inner)?;
+            }
+
+            EExprToken::EX_MapConst => {
+                self.property()?;
+                self.property()?;
+                self.count()?;
+                self.exprs_until(EExprToken::EX_EndMapConst, inner)?;
+            }
+
+            EExprToken::EX_ArrayConst => {
+                self.property()?;
+                self.count()?;
+                self.exprs_until(EExprToken::EX_EndArrayConst, inner)?;
+            }
+
+            EExprToken::EX_StructMemberContext | EExprToken::EX_LetValueOnPersistentFrame => {
+                self.property()?;
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_BindDelegate => {
+                // The function to bind, then the delegate and the object.
+                self.name()?;
+                self.expr(inner)?;
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_SwitchValue => {
+                let num_cases = self.u16()?;
+                write!(self.f, " ({} cases)", num_cases)?;
+                self.code_offset()?;
+
+                // The index, each case's value and result, then the default.
+                self.expr(inner)?;
+
+                for _ in 0..num_cases {
+                    self.expr(inner)?;
+                    self.line(self.offset, inner)?;
+                    self.f.write_str("next case")?;
+                    self.code_offset()?;
+                    self.expr(inner)?;
+                }
+
+                self.expr(inner)?;
+            }
+
+            EExprToken::EX_InstrumentationEvent => {
+                let event = self.u8()?;
+                write!(self.f, " event {}", event)?;
+
+                if event == INSTRUMENTATION_INLINE_EVENT {
+                    self.name()?;
+                }
+            }
+
+            EExprToken::EX_Nothing
+            | EExprToken::EX_EndParmValue
+            | EExprToken::EX_EndFunctionParms
+            | EExprToken::EX_Self
+            | EExprToken::EX_IntZero
+            | EExprToken::EX_IntOne
+            | EExprToken::EX_True
+            | EExprToken::EX_False
+            | EExprToken::EX_NoObject
+            | EExprToken::EX_NoInterface
+            | EExprToken::EX_EndStructConst
+            | EExprToken::EX_EndArray
+            | EExprToken::EX_EndSet
+            | EExprToken::EX_EndMap
+            | EExprToken::EX_EndSetConst
+            | EExprToken::EX_EndMapConst
+            | EExprToken::EX_EndArrayConst
+            | EExprToken::EX_PopExecutionFlow
+            | EExprToken::EX_Breakpoint
+            | EExprToken::EX_WireTracepoint
+            | EExprToken::EX_Tracepoint
+            | EExprToken::EX_EndOfScript
+            | EExprToken::EX_DeprecatedOp4A => {}
+
+            // Without its operand layout, there's no telling where the next
+            // expression starts.
+            _ => return Err(Stop::Malformed),
+        }
+
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disassemble(code: &[u8]) -> String {
+        Disassembly::new(code, RawReferences).to_string()
+    }
+
+    #[test]
+    fn nested_expressions_are_indented() {
+        let mut code = vec![EExprToken::EX_Let.0];
+        code.extend_from_slice(&0x1234_u64.to_le_bytes());
+        code.push(EExprToken::EX_LocalVariable.0);
+        code.extend_from_slice(&0x1234_u64.to_le_bytes());
+        code.push(EExprToken::EX_IntConst.0);
+        code.extend_from_slice(&(-7_i32).to_le_bytes());
+        code.push(EExprToken::EX_Return.0);
+        code.push(EExprToken::EX_Nothing.0);
+
+        assert_eq!(
+            disassemble(&code),
+            "000000 EX_Let 0x1234\n\
+             000009   EX_LocalVariable 0x1234\n\
+             000012   EX_IntConst -7\n\
+             000017 EX_Return\n\
+             000018   EX_Nothing"
+        );
+    }
+
+    #[test]
+    fn names_are_written_through_the_references() {
+        let mut code = vec![EExprToken::EX_NameConst.0];
+        code.extend_from_slice(&[5, 0, 0, 0, 5, 0, 0, 0, 2, 0, 0, 0]);
+
+        assert_eq!(disassemble(&code), "000000 EX_NameConst FName(5, 2)");
+    }
+
+    #[test]
+    fn truncated_operands_end_the_listing() {
+        let code = [EExprToken::EX_True.0, EExprToken::EX_IntConst.0, 1, 0];
+        assert_eq!(disassemble(&code), "000000 EX_True\n000001 EX_IntConst\n<truncated>");
+    }
+
+    #[test]
+    fn unknown_tokens_end_the_listing() {
+        let code = [EExprToken::EX_False.0, 0xFF, EExprToken::EX_True.0];
+        assert_eq!(disassemble(&code), "000000 EX_False\n000001 EX_Unknown(0xff)\n<can't decode further>");
+    }
+
+    #[test]
+    fn deep_nesting_is_malformed() {
+        let code = [EExprToken::EX_Return.0; 10_000];
+        assert!(disassemble(&code).ends_with("\n<can't decode further>"));
+    }
+}
@@ -0,0 +1,101 @@
+// Engine layouts that vary between Unreal Engine versions, or between custom
+// engine builds of the same version. Everything in `common` and `sdk_gen` that
+// pads over engine memory sizes its padding from `PROFILE`.
+//
+// The profile is chosen at build time through the `UE_VERSION` environment
+// variable (see build.rs). It defaults to 4.27, which is what DRG ships with.
+//
+// 4.27 is the only profile so far, because it's the only version we can check
+// against a live game. A profile is only added once its layouts have been
+// checked the same way. build.rs names the versions that are still missing
+// one and why.
+
+pub struct Profile {
+    pub version: &'static str,
+
+    // FNamePool
+    pub fname_max_block_bits: u8,
+    pub fname_block_offset_bits: u8,
+
+    // FUObjectArray
+    pub num_elements_per_chunk: usize,
+
+    // Bytes between `FUObjectArray::ObjObjects` and `MasterSerialNumber`:
+    // ObjObjectsCritical, ObjAvailableList, the create and delete listener
+    // arrays, and UObjectDeleteListenersCritical. Taken from the engine
+    // source and checked at init against the serial numbers already handed
+    // out, before anything writes to the counter.
+    pub fuobjectarray_size_before_master_serial_number: usize,
+
+    // Bytes after `UStruct::Script`: the property link chains,
+    // ScriptAndPropertyObjectReferences, UnresolvedScriptProperties and
+    // UnversionedSchema.
+    pub ustruct_trailing_size: usize,
+
+    // sizeof(UStruct).
+    pub ustruct_size: usize,
+
+    // Bytes between `UStruct` and `UClass::ClassFlags`: ClassConstructor,
+    // ClassVTableHelperCtorCaller, ClassAddReferencedObjects and ClassUnique.
+    pub uclass_size_before_class_flags: usize,
+
+    // Bytes after `UClass::Interfaces`: ReferenceTokenStream,
+    // ReferenceTokenStreamCritical and NativeFunctionLookupTable.
+    pub uclass_trailing_size: usize,
+
+    // sizeof(UClass).
+    pub uclass_size: usize,
+
+    // FFieldClass::Name.
+    pub ffield_class_size_before_id: usize,
+
+    // Bytes after `FFieldClass::CastFlags`.
+    pub ffield_class_trailing_size: usize,
+
+    // FField::Owner, an FFieldVariant.
+    pub ffield_owner_size: usize,
+
+    // Bytes after `FProperty::Offset`: RepNotifyFunc and the property link
+    // chains.
+    pub fproperty_trailing_size: usize,
+
+    // Bytes after `FArrayProperty::Inner`.
+    pub farray_property_trailing_size: usize,
+
+    // FMapProperty::MapLayout.
+    pub fmap_property_layout_size: usize,
+
+    // FSetProperty::SetLayout.
+    pub fset_property_layout_size: usize,
+
+    // Bytes between `UObject` and `UPackage::PIEInstanceID`.
+    pub upackage_size_before_pie_instance_id: usize,
+
+    // Bytes after `UPackage::PIEInstanceID`.
+    pub upackage_trailing_size: usize,
+}
+
+pub const UE4_27: Profile = Profile {
+    version: "4.27",
+    fname_max_block_bits: 13,
+    fname_block_offset_bits: 16,
+    num_elements_per_chunk: 64 * 1024,
+    fuobjectarray_size_before_master_serial_number: 248,
+    ustruct_trailing_size: 64,
+    ustruct_size: 0xB0,
+    uclass_size_before_class_flags: 28,
+    uclass_trailing_size: 72,
+    uclass_size: 0x230,
+    ffield_class_size_before_id: 8,
+    ffield_class_trailing_size: 40,
+    ffield_owner_size: 16,
+    fproperty_trailing_size: 40,
+    farray_property_trailing_size: 8,
+    fmap_property_layout_size: 32,
+    fset_property_layout_size: 24,
+    upackage_size_before_pie_instance_id: 56,
+    upackage_trailing_size: 60,
+};
+
+#[cfg(ue = "4.27")]
+pub const PROFILE: Profile = UE4_27;
@@ -0,0 +1,311 @@
+use core::char;
+use core::ffi::c_void;
+use core::fmt::{self, Display, Formatter, Write};
+use core::mem;
+use core::ops::Deref;
+use core::ptr;
+use core::slice;
+
+#[derive(macros::NoPanicErrorDebug)]
+pub enum Error {
+    TooLong,
+    MallocNotFound,
+    OutOfMemory,
+}
+
+// The engine's allocator, behind `FMemory::Malloc` and `FMemory::Free`. The
+// hook looks for it while it initializes. Until it's found, or if it isn't,
+// `FString::allocate` and `FString::free` fail.
+pub static mut GMalloc: *mut FMalloc = ptr::null_mut();
+
+#[repr(C)]
+pub struct FMalloc {
+    vtable: *const *const c_void,
+}
+
+impl FMalloc {
+    // FMalloc's virtual functions come after FExec's destructor and Exec.
+    const MALLOC_VTABLE_INDEX: usize = 2;
+    const FREE_VTABLE_INDEX: usize = 6;
+
+    // An `alignment` of 0 picks the allocator's default.
+    pub unsafe fn malloc(&mut self, size: usize, alignment: u32) -> *mut c_void {
+        type Malloc = unsafe extern "C" fn(*mut FMalloc, usize, u32) -> *mut c_void;
+        let malloc = mem::transmute::<*const c_void, Malloc>(*self.vtable.add(Self::MALLOC_VTABLE_INDEX));
+        malloc(self, size, alignment)
+    }
+
+    pub unsafe fn free(&mut self, original: *mut c_void) {
+        type Free = unsafe extern "C" fn(*mut FMalloc, *mut c_void);
+        let free = mem::transmute::<*const c_void, Free>(*self.vtable.add(Self::FREE_VTABLE_INDEX));
+        free(self, original);
+    }
+}
+
+unsafe fn gmalloc() -> Result<&'static mut FMalloc, Error> {
+    (*ptr::addr_of!(GMalloc)).as_mut().ok_or(Error::MallocNotFound)
+}
+
+// A UTF-16 string. Unless it's empty, `len` counts the null terminator.
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct FString {
+    data: *const u16,
+    pub len: i32,
+    pub capacity: i32,
+}
+
+impl FString {
+    // Copies `text` into memory from the engine's allocator, for a string that
+    // the engine will keep or free itself.
+    pub unsafe fn allocate(text: &str) -> Result<FString, Error> {
+        FString::allocate_units(text.encode_utf16())
+    }
+
+    // Like `allocate`, from code units without a null terminator.
+    pub unsafe fn allocate_units(units: impl Iterator<Item = u16> + Clone) -> Result<FString, Error> {
+        let malloc = gmalloc()?;
+        let len = units.clone().count() + 1;
+        let data = malloc.malloc(len * mem::size_of::<u16>(), 0).cast::<u16>();
+
+        if data.is_null() {
+            return Err(Error::OutOfMemory);
+        }
+
+        for (i, unit) in units.chain(Some(0)).enumerate() {
+            data.add(i).write(unit);
+        }
+
+        Ok(FString {
+            data,
+            len: len as i32,
+            capacity: len as i32,
+        })
+    }
+
+    // Gives an engine-allocated string's memory back to the engine, such as
+    // one from `allocate` that the engine didn't take, or one that it
+    // returned.
+    pub unsafe fn free(&mut self) -> Result<(), Error> {
+        if !self.data.is_null() {
+            gmalloc()?.free(self.data as *mut c_void);
+        }
+
+        *self = FString {
+            data: ptr::null(),
+            len: 0,
+            capacity: 0,
+        };
+
+        Ok(())
+    }
+
+    // The string's UTF-16 code units, without the null terminator. Nothing
+    // ties an `FString` to its buffer, so the caller has to know that it
+    // points at `len` live code units, like a string the engine owns.
+    pub unsafe fn as_slice(&self) -> &[u16] {
+        if self.data.is_null() || self.len <= 0 {
+            return &[];
+        }
+
+        without_terminator(slice::from_raw_parts(self.data, self.len as usize))
+    }
+
+    // Decodes the string, with U+FFFD in place of unpaired surrogates.
+    pub unsafe fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        decode(self.as_slice())
+    }
+
+    // Compares the way the engine's `FString` equality does by default.
+    pub unsafe fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.as_slice().len() == other.encode_utf16().count()
+            && self
+                .chars()
+                .zip(other.chars())
+                .all(|(a, b)| a.eq_ignore_ascii_case(&b))
+    }
+}
+
+fn without_terminator(units: &[u16]) -> &[u16] {
+    match units.split_last() {
+        Some((0, text)) => text,
+        _ => units,
+    }
+}
+
+fn decode(units: &[u16]) -> impl Iterator<Item = char> + '_ {
+    char::decode_utf16(units.iter().copied()).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+}
+
+// An `FString` that borrows its code units, so reading it is safe.
+#[derive(Copy, Clone)]
+pub struct FStr<'a> {
+    string: FString,
+    units: &'a [u16],
+}
+
+impl<'a> From<&'a [u16]> for FStr<'a> {
+    fn from(s: &'a [u16]) -> FStr<'a> {
+        FStr {
+            string: FString {
+                data: s.as_ptr(),
+                len: s.len() as i32,
+                capacity: s.len() as i32,
+            },
+            units: s,
+        }
+    }
+}
+
+impl<'a> FStr<'a> {
+    pub fn as_slice(&self) -> &'a [u16] {
+        without_terminator(self.units)
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        decode(self.as_slice())
+    }
+
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        unsafe { self.string.eq_ignore_ascii_case(other) }
+    }
+}
+
+// Passing the `FString` on is only sound while `self` is alive, e.g. as the
+// argument of an engine call that doesn't keep it.
+impl<'a> Deref for FStr<'a> {
+    type Target = FString;
+
+    fn deref(&self) -> &FString {
+        &self.string
+    }
+}
+
+impl<'a> Display for FStr<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> PartialEq<str> for FStr<'a> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_slice().iter().copied().eq(other.encode_utf16())
+    }
+}
+
+impl<'a, 'b> PartialEq<&'b str> for FStr<'a> {
+    fn eq(&self, other: &&'b str) -> bool {
+        *self == **other
+    }
+}
+
+// A null-terminated UTF-16 copy of a `&str` on the stack, for passing an
+// `FString` that the engine only reads during a call.
+pub struct FStringBuf<const N: usize> {
+    buffer: [u16; N],
+    len: usize,
+}
+
+impl<const N: usize> FStringBuf<N> {
+    // Fails if `text` and its null terminator need more than `N` code units.
+    pub fn new(text: &str) -> Result<FStringBuf<N>, Error> {
+        let mut buffer = [0; N];
+        let mut len = 0;
+
+        for unit in text.encode_utf16().chain(Some(0)) {
+            *buffer.get_mut(len).ok_or(Error::TooLong)? = unit;
+            len += 1;
+        }
+
+        Ok(FStringBuf { buffer, len })
+    }
+
+    // Copies as much of `units` as fits with a null terminator.
+    pub fn truncated(units: &[u16]) -> FStringBuf<N> {
+        let mut buffer = [0; N];
+        let len = units.len().min(N.saturating_sub(1));
+
+        if let (Some(to), Some(from)) = (buffer.get_mut(..len), units.get(..len)) {
+            to.copy_from_slice(from);
+        }
+
+        FStringBuf {
+            buffer,
+            len: if N == 0 { 0 } else { len + 1 },
+        }
+    }
+
+    // The code units, without the null terminator.
+    pub fn as_slice(&self) -> &[u16] {
+        self.as_fstring().as_slice()
+    }
+
+    pub fn as_fstring(&self) -> FStr<'_> {
+        self.buffer.get(..self.len).unwrap_or_default().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buf_counts_the_null_terminator() {
+        let buf = FStringBuf::<5>::new("noob").unwrap();
+        let string = buf.as_fstring();
+
+        assert_eq!(string.len, 5);
+        assert_eq!(string.as_slice(), "noob".encode_utf16().collect::<Vec<_>>());
+        assert!(string == "noob");
+        assert!(string.eq_ignore_ascii_case("NoOb"));
+        assert!(!string.eq_ignore_ascii_case("noo"));
+    }
+
+    #[test]
+    fn buf_rejects_text_without_room_for_the_terminator() {
+        assert!(matches!(FStringBuf::<4>::new("noob"), Err(Error::TooLong)));
+        assert!(matches!(FStringBuf::<0>::new(""), Err(Error::TooLong)));
+    }
+
+    #[test]
+    fn buf_holds_the_empty_string() {
+        let buf = FStringBuf::<1>::new("").unwrap();
+        let string = buf.as_fstring();
+
+        assert_eq!(string.len, 1);
+        assert!(string.as_slice().is_empty());
+        assert_eq!(string.to_string(), "");
+    }
+
+    #[test]
+    fn buf_encodes_surrogate_pairs() {
+        assert!(matches!(FStringBuf::<2>::new("🦀"), Err(Error::TooLong)));
+
+        let buf = FStringBuf::<3>::new("🦀").unwrap();
+        let string = buf.as_fstring();
+
+        assert_eq!(string.as_slice().len(), 2);
+        assert_eq!(string.to_string(), "🦀");
+    }
+
+    #[test]
+    fn truncated_keeps_room_for_the_terminator() {
+        let units: Vec<u16> = "noob".encode_utf16().collect();
+
+        assert_eq!(FStringBuf::<3>::truncated(&units).as_slice(), &units[..2]);
+        assert_eq!(FStringBuf::<3>::truncated(&units).as_fstring().len, 3);
+        assert_eq!(FStringBuf::<8>::truncated(&units).as_slice(), &units[..]);
+        assert!(FStringBuf::<1>::truncated(&units).as_slice().is_empty());
+        assert!(FStringBuf::<0>::truncated(&units).as_slice().is_empty());
+    }
+
+    #[test]
+    fn unpaired_surrogates_decode_to_replacement_characters() {
+        let units = [u16::from(b'a'), 0xD800, u16::from(b'b')];
+        assert_eq!(FStr::from(&units[..]).to_string(), "a\u{FFFD}b");
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{FName, TArray, TPair};
+
+use core::iter;
+use core::slice;
+
+// Read-only views of the engine's hash containers, enough to look things up in
+// maps the engine owns, such as `UClass::FuncMap`.
+// Engine\Source\Runtime\Core\Public\Containers\Map.h, Set.h and SparseArray.h
+
+#[repr(C)]
+pub struct TMap<K, V> {
+    Pairs: TSet<TPair<K, V>>,
+}
+
+#[repr(C)]
+pub struct TSet<T> {
+    Elements: TSparseArray<TSetElement<T>>,
+    Hash: FHashAllocator,
+    HashSize: i32,
+}
+
+// An element slot, or a link in the list of free slots if the slot is empty.
+// The free list link is smaller than any set element, so it isn't modeled.
+#[repr(C)]
+struct TSetElement<T> {
+    Value: T,
+    HashNextId: i32,
+    HashIndex: i32,
+}
+
+#[repr(C)]
+struct TSparseArray<T> {
+    Data: TArray<T>,
+    AllocationFlags: TBitArray,
+    FirstFreeIndex: i32,
+    NumFreeIndices: i32,
+}
+
+#[repr(C)]
+struct TBitArray {
+    InlineData: [u32; 4],
+    SecondaryData: *const u32,
+    NumBits: i32,
+    MaxBits: i32,
+}
+
+// The hash buckets: indices of the first element in each bucket's chain. A
+// set with one bucket keeps it inline.
+#[repr(C)]
+struct FHashAllocator {
+    InlineData: [i32; 1],
+    SecondaryData: *const i32,
+}
+
+impl TBitArray {
+    unsafe fn get(&self, index: usize) -> bool {
+        let words = if self.SecondaryData.is_null() {
+            self.InlineData.as_ptr()
+        } else {
+            self.SecondaryData
+        };
+
+        *words.add(index / 32) & (1 << (index % 32)) != 0
+    }
+}
+
+impl<T> TSet<T> {
+    pub fn len(&self) -> usize {
+        (self.Elements.Data.len - self.Elements.NumFreeIndices) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub unsafe fn iter(&self) -> impl Iterator<Item = &T> {
+        let elements: &[TSetElement<T>] = &self.Elements.Data;
+        let flags = &self.Elements.AllocationFlags;
+
+        elements
+            .iter()
+            .enumerate()
+            .filter(move |(index, _)| flags.get(*index))
+            .map(|(_, element)| &element.Value)
+    }
+
+    // The elements chained in the bucket for `hash`, which include any
+    // element whose key hashes to `hash`.
+    unsafe fn bucket(&self, hash: u32) -> impl Iterator<Item = &T> {
+        let buckets = if self.Hash.SecondaryData.is_null() {
+            self.Hash.InlineData.as_ptr()
+        } else {
+            self.Hash.SecondaryData
+        };
+
+        let num_buckets = self.HashSize.max(0) as usize;
+        let buckets = slice::from_raw_parts(buckets, num_buckets);
+        let elements: &[TSetElement<T>] = &self.Elements.Data;
+
+        // The bucket count is a power of two.
+        let mut id = buckets
+            .get(hash as usize & num_buckets.wrapping_sub(1))
+            .copied()
+            .unwrap_or(-1);
+
+        iter::from_fn(move || {
+            let element = elements.get(usize::try_from(id).ok()?)?;
+            id = element.HashNextId;
+            Some(&element.Value)
+        })
+    }
+}
+
+impl<K, V> TMap<K, V> {
+    pub fn len(&self) -> usize {
+        self.Pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.Pairs.is_empty()
+    }
+
+    pub unsafe fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.Pairs.iter().map(|pair| (&pair.Key, &pair.Value))
+    }
+}
+
+impl<V> TMap<FName, V> {
+    pub unsafe fn get(&self, key: FName) -> Option<&V> {
+        self.Pairs
+            .bucket(key.type_hash())
+            .find(|pair| pair.Key == key)
+            .map(|pair| &pair.Value)
+    }
+}
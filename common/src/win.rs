@@ -39,7 +39,11 @@ extern "system" {
         creation_flags: u32,
         thread_id: *mut u32,
     ) -> *mut c_void;
+    fn DeleteFileA(file_name: *const u8) -> i32;
     fn DisableThreadLibraryCalls(dll: *mut c_void) -> i32;
+    fn FindClose(find_file: *mut c_void) -> i32;
+    fn FindFirstFileA(file_name: *const u8, find_file_data: *mut file::FindData) -> *mut c_void;
+    fn FindNextFileA(find_file: *mut c_void, find_file_data: *mut file::FindData) -> i32;
     fn FlushFileBuffers(file: *mut c_void) -> i32;
     pub fn FlushInstructionCache(
         hProcess: *mut c_void,
@@ -51,6 +55,7 @@ extern "system" {
     pub fn GetCurrentProcess() -> *mut c_void;
     pub fn GetModuleHandleA(module_name: *const u8) -> *mut c_void;
     pub fn GetStdHandle(std_handle: u32) -> *mut c_void;
+    fn MoveFileExA(existing_file_name: *const u8, new_file_name: *const u8, flags: u32) -> i32;
     pub fn ReadConsoleA(
         console_input: *mut c_void,
         buffer: *mut u8,
@@ -58,6 +63,13 @@ extern "system" {
         num_read: *mut u32,
         input_control: *mut c_void,
     ) -> i32;
+    fn ReadFile(
+        file: *mut c_void,
+        buffer: *mut u8,
+        number_of_bytes_to_read: u32,
+        number_of_bytes_read: *mut u32,
+        overlapped: *mut c_void,
+    ) -> i32;
     pub fn Sleep(dwMilliseconds: u32);
     pub fn QueryPerformanceCounter(lpPerformanceCount: *mut i64) -> i32;
     pub fn QueryPerformanceFrequency(lpFrequency: *mut i64) -> i32;
@@ -2,10 +2,16 @@ use core::ffi::c_void;
 use core::fmt::{self, Write};
 use core::ptr;
 
+const INVALID_HANDLE_VALUE: usize = usize::MAX;
+
 #[derive(macros::NoPanicErrorDebug)]
 pub enum Error {
     CreateFile,
     WriteFile,
+    OpenFile,
+    ReadFile,
+    DeleteFile,
+    MoveFile,
 }
 
 pub struct File {
@@ -14,7 +20,6 @@ pub struct File {
 
 impl File {
     pub unsafe fn new<T: AsRef<[u8]>>(name: T) -> Result<Self, Error> {
-        const INVALID_HANDLE_VALUE: usize = usize::MAX;
         const GENERIC_WRITE: u32 = 0x4000_0000;
         const FILE_SHARE_READ: u32 = 1;
         const CREATE_ALWAYS: u32 = 2;
@@ -37,6 +42,78 @@ impl File {
         Ok(Self { handle })
     }
 
+    // Opens an existing file for reading.
+    pub unsafe fn open<T: AsRef<[u8]>>(name: T) -> Result<Self, Error> {
+        const GENERIC_READ: u32 = 0x8000_0000;
+        const FILE_SHARE_READ: u32 = 1;
+        const OPEN_EXISTING: u32 = 3;
+        const FILE_ATTRIBUTE_NORMAL: u32 = 0x80;
+
+        let handle = super::CreateFileA(
+            name.as_ref().as_ptr(),
+            GENERIC_READ,
+            FILE_SHARE_READ,
+            ptr::null_mut(),
+            OPEN_EXISTING,
+            FILE_ATTRIBUTE_NORMAL,
+            ptr::null_mut(),
+        );
+
+        if handle as usize == INVALID_HANDLE_VALUE {
+            return Err(Error::OpenFile);
+        }
+
+        Ok(Self { handle })
+    }
+
+    pub unsafe fn delete<T: AsRef<[u8]>>(name: T) -> Result<(), Error> {
+        if super::DeleteFileA(name.as_ref().as_ptr()) == 0 {
+            Err(Error::DeleteFile)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Moves the file at `from` to `to`, replacing whatever file is at `to`.
+    pub unsafe fn replace<T: AsRef<[u8]>, U: AsRef<[u8]>>(from: T, to: U) -> Result<(), Error> {
+        const MOVEFILE_REPLACE_EXISTING: u32 = 1;
+
+        let result = super::MoveFileExA(
+            from.as_ref().as_ptr(),
+            to.as_ref().as_ptr(),
+            MOVEFILE_REPLACE_EXISTING,
+        );
+
+        if result == 0 {
+            Err(Error::MoveFile)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Reads into `buffer`, returning how many bytes were read. Zero means the
+    // end of the file.
+    pub fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        unsafe {
+            let mut num_read = 0;
+
+            #[allow(clippy::cast_possible_truncation)]
+            let result = super::ReadFile(
+                self.handle,
+                buffer.as_mut_ptr(),
+                buffer.len() as u32,
+                &mut num_read,
+                ptr::null_mut(),
+            );
+
+            if result == 0 {
+                Err(Error::ReadFile)
+            } else {
+                Ok(num_read as usize)
+            }
+        }
+    }
+
     pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
         unsafe {
             let mut num_written = 0;
@@ -73,3 +150,76 @@ impl Write for File {
         self.write_bytes(s.as_bytes()).map_err(|_| fmt::Error)
     }
 }
+
+// WIN32_FIND_DATAA
+#[allow(dead_code)]
+#[repr(C)]
+pub struct FindData {
+    file_attributes: u32,
+    creation_time: [u32; 2],
+    last_access_time: [u32; 2],
+    last_write_time: [u32; 2],
+    file_size_high: u32,
+    file_size_low: u32,
+    reserved0: u32,
+    reserved1: u32,
+    file_name: [u8; 260],
+    alternate_file_name: [u8; 14],
+}
+
+// The names of the files, but not directories, that match a pattern such as
+// `C:\dir\*.rs`.
+pub struct FindFiles {
+    handle: *mut c_void,
+    data: FindData,
+    is_first: bool,
+}
+
+impl FindFiles {
+    pub unsafe fn new<T: AsRef<[u8]>>(pattern: T) -> FindFiles {
+        let mut files = FindFiles {
+            handle: ptr::null_mut(),
+            data: core::mem::zeroed(),
+            is_first: true,
+        };
+
+        files.handle = super::FindFirstFileA(pattern.as_ref().as_ptr(), &mut files.data);
+        files
+    }
+
+    fn is_valid(&self) -> bool {
+        self.handle as usize != INVALID_HANDLE_VALUE
+    }
+
+    pub unsafe fn next(&mut self) -> Option<&[u8]> {
+        const FILE_ATTRIBUTE_DIRECTORY: u32 = 0x10;
+
+        loop {
+            if !self.is_valid() {
+                return None;
+            }
+
+            if self.is_first {
+                self.is_first = false;
+            } else if super::FindNextFileA(self.handle, &mut self.data) == 0 {
+                return None;
+            }
+
+            if self.data.file_attributes & FILE_ATTRIBUTE_DIRECTORY == 0 {
+                let name = &self.data.file_name;
+                let len = name.iter().position(|&c| c == 0).unwrap_or(name.len());
+                return name.get(..len);
+            }
+        }
+    }
+}
+
+impl Drop for FindFiles {
+    fn drop(&mut self) {
+        if self.is_valid() {
+            unsafe {
+                super::FindClose(self.handle);
+            }
+        }
+    }
+}
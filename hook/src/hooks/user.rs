@@ -1,16 +1,23 @@
 use common::win::random;
-use common::{self, EClassCastFlags, FFrame, List, UFunction, UObject};
+use common::{self, EClassCastFlags, FFrame, FStringBuf, Handle, HookParameters, List, UFunction, UObject};
 use core::ffi::c_void;
 use core::mem;
+use sdk::blueprint_generated::{
+    BP_PlayerCharacter_C, BP_PlayerCharacter_C_InpActEvt_Delete_K2Node_InputKeyEvent_Params,
+    BP_PlayerCharacter_C_InpActEvt_Insert_K2Node_InputKeyEvent_Params,
+};
 use sdk::Engine::{Actor, LocalPlayer};
-use sdk::FSD::{FSDCheatManager, FSDPlayerController, PlayerCharacter};
+use sdk::FSD::{
+    AmmoCountWidget, AmmoCountWidget_OnItemAmountChanged_Params, FSDCheatManager,
+    FSDPlayerController, Item, Item_GetItemName_Params, PlayerCharacter,
+};
 
 mod pawn;
 mod weapon;
 
 mod render;
 
-pub static mut SEEN_FUNCTIONS: List<*mut UFunction, 4096> = List::new();
+pub static mut SEEN_FUNCTIONS: List<Handle<UFunction>, 4096> = List::new();
 
 pub struct OneTimeModifications;
 
@@ -29,9 +36,10 @@ impl Drop for OneTimeModifications {
 }
 
 #[allow(dead_code)]
-unsafe fn set_custom_name(controller: *mut FSDPlayerController) {
-    const NAME: [u16; 5] = [0x6e, 0x6f, 0x6f, 0x62, 0];
-    (*controller).ServerChangeName(NAME.as_slice().into());
+unsafe fn set_custom_name(controller: *mut FSDPlayerController) -> Result<(), common::Error> {
+    let name = FStringBuf::<5>::new("noob")?;
+    (*controller).ServerChangeName(*name.as_fstring());
+    Ok(())
 }
 
 pub unsafe extern "C" fn my_process_remote_function_for_channel(
@@ -120,45 +128,32 @@ pub unsafe extern "C" fn my_add_cheats(controller: *mut FSDPlayerController, _:
     original(controller, true);
 }
 
-pub unsafe extern "C" fn my_on_item_amount_changed(
-    context: *mut UObject,
-    stack: *mut FFrame,
-    result: *mut c_void,
+pub unsafe fn on_item_amount_changed(
+    widget: &mut AmmoCountWidget,
+    _: &mut HookParameters<AmmoCountWidget_OnItemAmountChanged_Params>,
 ) {
-    weapon::on_item_amount_changed(context.cast());
-    (*super::ON_ITEM_AMOUNT_CHANGED.as_ptr())(context, stack, result);
+    weapon::on_item_amount_changed(widget);
 }
 
-pub unsafe extern "C" fn my_get_item_name(
-    context: *mut UObject,
-    stack: *mut FFrame,
-    result: *mut c_void,
-) {
-    weapon::on_item_equipped(context.cast());
-    (*super::GET_ITEM_NAME.as_ptr())(context, stack, result);
+pub unsafe fn on_get_item_name(item: &mut Item, _: &mut HookParameters<Item_GetItemName_Params>) {
+    weapon::on_item_equipped(item);
 }
 
-// pub unsafe extern "C" fn my_on_flare(
-//     context: *mut UObject,
-//     stack: *mut FFrame,
-//     result: *mut c_void,
+// pub unsafe fn on_flare(
+//     widget: &mut HUD_Flares_C,
+//     _: &mut HookParameters<HUD_Flares_C_OnFlareCountChanged_Params>,
 // ) {
-//     let widget = context.cast::<FSDUserWidget>();
-//     let character = (*widget).Character;
+//     let character = widget.Character;
 //     let inv = (*character).InventoryComponent;
 //     (*inv).FlareProductionTime = 0.0;
-//     (*super::ON_FLARE.as_ptr())(context, stack, result);
 // }
 
-pub unsafe extern "C" fn my_on_keypress_insert(
-    context: *mut UObject,
-    stack: *mut FFrame,
-    result: *mut c_void,
+pub unsafe fn on_keypress_insert(
+    character: &mut BP_PlayerCharacter_C,
+    _: &mut HookParameters<BP_PlayerCharacter_C_InpActEvt_Insert_K2Node_InputKeyEvent_Params>,
 ) {
-    let character = context.cast::<PlayerCharacter>();
-    let health = (*character).HealthComponent;
+    let health = character.HealthComponent;
     (*health).ToggleCanTakeDamage();
-    (*super::ON_KEYPRESS_INSERT.as_ptr())(context, stack, result);
 }
 
 #[allow(dead_code)]
@@ -174,13 +169,11 @@ unsafe fn get_game_data() -> *mut sdk::FSD::GameData {
     }
 }
 
-pub unsafe extern "C" fn my_on_keypress_delete(
-    context: *mut UObject,
-    stack: *mut FFrame,
-    result: *mut c_void,
+pub unsafe fn on_keypress_delete(
+    _: &mut BP_PlayerCharacter_C,
+    _: &mut HookParameters<BP_PlayerCharacter_C_InpActEvt_Delete_K2Node_InputKeyEvent_Params>,
 ) {
     render::toggle_lighting();
-    (*super::ON_KEYPRESS_DELETE.as_ptr())(context, stack, result);
 }
 
 #[allow(dead_code)]
@@ -199,7 +192,7 @@ pub unsafe extern "C" fn my_post_actor_construction(actor: *mut Actor) {
 #[allow(dead_code)]
 unsafe fn print_if_unseen(object: *mut UObject, function: *mut UFunction) {
     if (*function).seen_count == 0 {
-        if SEEN_FUNCTIONS.push(function).is_ok() {
+        if SEEN_FUNCTIONS.push(Handle::new(function)).is_ok() {
             (*function).seen_count = 1;
             common::log!("{} {}", *object, *function);
         } else {
@@ -47,6 +47,9 @@ pub unsafe fn toggle_lighting() {
     match get_view_mode_index() {
         Ok(ViewModeIndex::Unlit) => restore_lighting(),
         Ok(ViewModeIndex::Lit) => remove_lighting(),
-        Err(e) => common::log!("toggle_lighting() error: {:?}", e),
+        Err(e) => {
+            common::log!("toggle_lighting() error: {:?}{}", e, common::context::Chain);
+            common::context::clear();
+        }
     }
 }
\ No newline at end of file
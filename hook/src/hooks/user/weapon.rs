@@ -6,13 +6,13 @@ pub unsafe fn on_item_amount_changed(widget: *mut AmmoCountWidget) {
 
     let item = (*widget).Item.cast::<UObject>();
 
-    if (*item).is(AMMO_DRIVEN_WEAPON) {
+    if is(item, AMMO_DRIVEN_WEAPON) {
         let weapon = item.cast::<AmmoDrivenWeapon>();
 
         if (*weapon).AmmoCount < (*weapon).ClipSize {
             (*weapon).AmmoCount = (*weapon).ClipSize;
         }
-    } else if (*item).is(DOUBLE_DRILL_ITEM) {
+    } else if is(item, DOUBLE_DRILL_ITEM) {
         let drill = item.cast::<DoubleDrillItem>();
 
         if (*drill).Fuel < 2.0 {
@@ -30,17 +30,17 @@ pub unsafe fn on_item_equipped(item: *mut Item) {
     
     let item = item.cast::<UObject>();
 
-    if (*item).is(AMMO_DRIVEN_WEAPON) {
+    if is(item, AMMO_DRIVEN_WEAPON) {
         let weapon = item.cast();
 
         no_recoil(weapon);
 
         let fire = (*weapon).WeaponFire.cast::<UObject>();
         
-        if !fire.is_null() && (*fire).is(HITSCAN_BASE_COMPONENT) {
+        if !fire.is_null() && is(fire, HITSCAN_BASE_COMPONENT) {
             no_spread(fire.cast());
         }
-    } else if (*item).is(THROWN_GRENADE_ITEM) {
+    } else if is(item, THROWN_GRENADE_ITEM) {
         let item = item.cast::<ThrownGrenadeItem>();
         (*item).Server_Resupply(1.0);
     }
@@ -67,12 +67,12 @@ pub unsafe fn no_recoil(weapon: *mut AmmoDrivenWeapon) {
 
 pub unsafe fn is_server_register_hit(function: *mut UFunction) -> bool {
     use crate::hooks::*;
-    function == SERVER_REGISTER_HIT || 
-    function == SERVER_REGISTER_HIT_MULTI ||
-    function == SERVER_REGISTER_HIT_TERRAIN ||
-    function == SERVER_REGISTER_HIT_DESTRUCTABLE ||
-    function == SERVER_REGISTER_RICOCHET_HIT ||
-    function == SERVER_REGISTER_RICOCHET_HIT_TERRAIN ||
-    function == SERVER_REGISTER_RICOCHET_HIT_DESTRUCTABLE ||
-    function == SERVER_DAMAGE_TARGET
+    function == SERVER_REGISTER_HIT.ptr() || 
+    function == SERVER_REGISTER_HIT_MULTI.ptr() ||
+    function == SERVER_REGISTER_HIT_TERRAIN.ptr() ||
+    function == SERVER_REGISTER_HIT_DESTRUCTABLE.ptr() ||
+    function == SERVER_REGISTER_RICOCHET_HIT.ptr() ||
+    function == SERVER_REGISTER_RICOCHET_HIT_TERRAIN.ptr() ||
+    function == SERVER_REGISTER_RICOCHET_HIT_DESTRUCTABLE.ptr() ||
+    function == SERVER_DAMAGE_TARGET.ptr()
 }
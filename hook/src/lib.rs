@@ -10,7 +10,7 @@ extern "C" {}
 #[link(name = "vcruntime")]
 extern "C" {}
 
-use common::{self, win};
+use common::{self, win, Context};
 use core::ffi::c_void;
 use core::ptr;
 use sdk::Engine::Engine;
@@ -25,6 +25,8 @@ enum Error {
     Hooks(#[from] hooks::Error),
     FindGlobalEngine,
     FindFunctionInvoke,
+    FindGNatives,
+    FindGMalloc,
     FindProcessRemoteFunctionForChannel,
     FindAddCheats,
     FindPostActorConstruction,
@@ -49,7 +51,8 @@ unsafe extern "system" fn on_attach(dll: *mut c_void) -> u32 {
     win::AllocConsole();
 
     if let Err(e) = run() {
-        common::log!("error: {:?}", e);
+        common::log!("error: {:?}{}", e, common::context::Chain);
+        common::context::clear();
         common::idle();
     }
 
@@ -66,7 +69,7 @@ unsafe fn run() -> Result<(), Error> {
     init_globals(&module)?;
 
     {
-        let _hooks = Hooks::new(&module)?;
+        let _hooks = Hooks::new(&module).context("hook installation")?;
         common::idle();
     }
 
@@ -77,6 +80,8 @@ unsafe fn init_globals(module: &win::Module) -> Result<(), Error> {
     common::init_globals(module)?;
     find_global_engine(module)?;
     find_function_invoke(module)?;
+    warn_if_missing(find_gnatives(module));
+    warn_if_missing(find_gmalloc(module));
     find_process_remote_function_for_channel(module)?;
     find_add_cheats(module)?;
     // find_post_actor_construction(module)?;
@@ -84,6 +89,15 @@ unsafe fn init_globals(module: &win::Module) -> Result<(), Error> {
     Ok(())
 }
 
+// For globals that only some hooks need. Those hooks fail without them, but
+// the rest still work.
+unsafe fn warn_if_missing(result: Result<(), Error>) {
+    if let Err(e) = result {
+        common::log!("warning: {:?}{}", e, common::context::Chain);
+        common::context::clear();
+    }
+}
+
 unsafe fn find_global_engine(module: &win::Module) -> Result<(), Error> {
     // 00007FF72626A8F5 | 48:8B0D 64353105         | mov rcx,qword ptr ds:[7FF72B57DE60]     |
     // 00007FF72626A8FC | 49:8BD6                  | mov rdx,r14                             |
@@ -142,6 +156,108 @@ unsafe fn find_function_invoke(module: &win::Module) -> Result<(), Error> {
     Ok(())
 }
 
+unsafe fn find_gnatives(module: &win::Module) -> Result<(), Error> {
+    // FFrame::Step
+    // 48:8B41 20               | mov rax,qword ptr ds:[rcx+20]           | Code
+    // 4C:8BD2                  | mov r10,rdx                             |
+    // 48:8BD1                  | mov rdx,rcx                             |
+    // 44:0FB608                | movzx r9d,byte ptr ds:[rax]             |
+    // 48:FFC0                  | inc rax                                 |
+    // 48:8941 20               | mov qword ptr ds:[rcx+20],rax           |
+    // 48:8D05 ????????         | lea rax,qword ptr ds:[GNatives]         |
+    // 49:8BCA                  | mov rcx,r10                             |
+    // 4A:FF24C8                | jmp qword ptr ds:[rax+r9*8]             |
+    const PATTERN: [Option<u8>; 34] = [
+        Some(0x48),
+        Some(0x8B),
+        Some(0x41),
+        Some(0x20),
+        Some(0x4C),
+        Some(0x8B),
+        Some(0xD2),
+        Some(0x48),
+        Some(0x8B),
+        Some(0xD1),
+        Some(0x44),
+        Some(0x0F),
+        Some(0xB6),
+        Some(0x08),
+        Some(0x48),
+        Some(0xFF),
+        Some(0xC0),
+        Some(0x48),
+        Some(0x89),
+        Some(0x41),
+        Some(0x20),
+        Some(0x48),
+        Some(0x8D),
+        Some(0x05),
+        None,
+        None,
+        None,
+        None,
+        Some(0x49),
+        Some(0x8B),
+        Some(0xCA),
+        Some(0x4A),
+        Some(0xFF),
+        Some(0x24),
+    ];
+    let step: *const u8 = module.find(&PATTERN).ok_or(Error::FindGNatives)?;
+    let lea_rax_gnatives = step.add(21);
+    let relative_offset = lea_rax_gnatives.add(3).cast::<i32>().read_unaligned();
+    common::GNatives = lea_rax_gnatives.offset(7 + relative_offset as isize).cast();
+    Ok(())
+}
+
+unsafe fn find_gmalloc(module: &win::Module) -> Result<(), Error> {
+    // FMemory::Free
+    // 48:85C9                  | test rcx,rcx                            |
+    // 74 ??                    | je                                      |
+    // 53                       | push rbx                                |
+    // 48:83EC 20               | sub rsp,20                              |
+    // 48:8BD9                  | mov rbx,rcx                             |
+    // 48:8B0D ????????         | mov rcx,qword ptr ds:[GMalloc]          |
+    // 48:85C9                  | test rcx,rcx                            |
+    const PATTERN: [Option<u8>; 23] = [
+        Some(0x48),
+        Some(0x85),
+        Some(0xC9),
+        Some(0x74),
+        None,
+        Some(0x53),
+        Some(0x48),
+        Some(0x83),
+        Some(0xEC),
+        Some(0x20),
+        Some(0x48),
+        Some(0x8B),
+        Some(0xD9),
+        Some(0x48),
+        Some(0x8B),
+        Some(0x0D),
+        None,
+        None,
+        None,
+        None,
+        Some(0x48),
+        Some(0x85),
+        Some(0xC9),
+    ];
+    let free: *const u8 = module.find(&PATTERN).ok_or(Error::FindGMalloc)?;
+    let mov_rcx_gmalloc = free.add(13);
+    let relative_offset = mov_rcx_gmalloc.add(3).cast::<i32>().read_unaligned();
+    common::GMalloc = *mov_rcx_gmalloc
+        .offset(7 + relative_offset as isize)
+        .cast::<*mut common::FMalloc>();
+
+    if common::GMalloc.is_null() {
+        Err(Error::FindGMalloc)
+    } else {
+        Ok(())
+    }
+}
+
 unsafe fn find_process_remote_function_for_channel(module: &win::Module) -> Result<(), Error> {
     const PATTERN: [Option<u8>; 19] = [
         Some(0x48),
@@ -1,10 +1,12 @@
+use crate::config::BUF_WRITER_CAPACITY;
+
 use common::List;
 use core::fmt::{self, Write};
 use core::str;
 
 pub struct BufWriter<W: Write> {
     writer: W,
-    buffer: List<u8, 8192>,
+    buffer: List<u8, BUF_WRITER_CAPACITY>,
 }
 
 impl<W: Write> BufWriter<W> {
@@ -15,7 +17,11 @@ impl<W: Write> BufWriter<W> {
         }
     }
 
-    fn flush(&mut self) -> Result<(), fmt::Error> {
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub fn flush(&mut self) -> Result<(), fmt::Error> {
         let s = unsafe { str::from_utf8_unchecked(self.buffer.as_slice()) };
         self.writer.write_str(s)?;
         self.buffer.clear();
@@ -0,0 +1,44 @@
+// Fixed capacities of the generator. Nothing is heap allocated, so these bound
+// how big a game the generator can handle. Raise them if a run reports
+// overflows.
+
+// Packages that get their own module in the SDK.
+pub const MAX_PACKAGES: usize = 256;
+
+// Slots in the package-to-module lookup table. Keep it well above
+// `MAX_PACKAGES` so probes stay short.
+pub const PACKAGE_INDICES_CAPACITY: usize = 2 * MAX_PACKAGES;
+
+// Bitfield storage fields per struct.
+pub const MAX_BITFIELDS: usize = 64;
+
+// Bools that share one bitfield storage field.
+pub const MAX_BOOLS_PER_BITFIELD: usize = 64;
+
+// Parameters, including return values, per function.
+pub const MAX_PARAMETERS: usize = 32;
+
+// Length of the type a struct derefs to, e.g. `Engine_Actor`.
+pub const MAX_INHERITED_TYPE_LEN: usize = 128;
+
+// Length of a file path, including the nul terminator.
+pub const MAX_PATH: usize = 260;
+
+// Bytes buffered before each write to a module file.
+pub const BUF_WRITER_CAPACITY: usize = 8 * 1024;
+
+// Slots in the table of (module, imported type) pairs.
+pub const IMPORTS_CAPACITY: usize = 1 << 16;
+
+// Slots in the table of type name counts.
+pub const TYPE_NAMES_CAPACITY: usize = 1 << 17;
+
+// Slots in the table of parameter struct names, one per generated function.
+pub const PARAMETERS_NAMES_CAPACITY: usize = 1 << 17;
+
+// Types one module imports from all other modules.
+pub const MAX_MODULE_IMPORTS: usize = 8192;
+
+// Whether to leave out an item that overflows one of the capacities above, and
+// note it in the generation report, instead of aborting the whole run.
+pub const SKIP_ON_OVERFLOW: bool = true;
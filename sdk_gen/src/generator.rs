@@ -1,58 +1,70 @@
 use crate::buf_writer::BufWriter;
-use crate::game::{
-    self, EPropertyFlags, FBoolProperty, FProperty, PropertyDisplayable, TPair, UEnum,
+use crate::config::{
+    self, MAX_BITFIELDS, MAX_BOOLS_PER_BITFIELD, MAX_INHERITED_TYPE_LEN, MAX_PACKAGES,
+    MAX_PARAMETERS, MAX_PATH, PACKAGE_INDICES_CAPACITY, PARAMETERS_NAMES_CAPACITY,
 };
+use crate::game::{self, PropertyDisplayable};
+use crate::imports::{self, Module, TypeName};
+use crate::module_file::{self, ModuleFile};
+use crate::report::{Counts, Issue, Report};
 use crate::{sdk_file, sdk_path};
 
-use common::win::file::{self, File};
-use common::{Hex, List, SplitIterator};
+use common::win::file::{self, File, FindFiles};
+use common::{Context, FnvHasher, FullNameOf, Hex, List, Map, SplitIterator};
 use common::{
-    EClassCastFlags, FName, GUObjectArray, UClass, UFunction, UObject, UPackage, UStruct,
+    EClassCastFlags, FBoolProperty, FName, FProperty, GUObjectArray, ParameterDirection, TPair,
+    UClass, UEnum, UFunction, UObject, UPackage, UStruct,
 };
 
 use core::cell::Cell;
 use core::cmp::Ordering;
 use core::fmt::{self, Display, Formatter, Write};
+use core::hash::Hasher;
+use core::ptr;
 use core::str;
 
 #[derive(macros::NoPanicErrorDebug)]
 pub enum Error {
     Game(#[from] game::Error),
     File(#[from] file::Error),
+    Imports(#[from] imports::Error),
+    ModuleFile(#[from] module_file::Error),
     Fmt(#[from] fmt::Error),
 
     ZeroSizedField,
     BadBitfieldSize(u8),
     LastBitfield,
-    MaxPackages,
-    MaxBitfields,
-    BitfieldFull,
+    MaxPackages(FullNameOf),
+    MaxBitfields(FullNameOf),
+    BitfieldFull(FullNameOf),
 
-    MaxParameters,
+    MaxParameters(FullNameOf),
+    MaxParametersNames,
 }
 
-struct Package {
-    ptr: *mut UPackage,
-    file: File,
-}
+// The parameter struct names taken in each module, hashed. A name joins two
+// identifiers, so class `A_B`'s function `C` and class `A`'s function `B_C`
+// would both get `A_B_C_Params`. Every name after the first gets a number.
+static mut PARAMETERS_NAMES: Map<(Module, u64), (), PARAMETERS_NAMES_CAPACITY> = Map::new();
 
-impl Drop for Package {
-    fn drop(&mut self) {
-        unsafe {
-            (*self.ptr).PIEInstanceID = -1;
-        }
-    }
+struct Package {
+    ptr: *const UPackage,
+    file: ModuleFile,
+    counts: Counts,
 }
 
 pub struct Generator {
-    lib_rs: File,
-    packages: List<Package, 256>,
-    blueprint_generated_package_file: BufWriter<File>,
+    lib_rs: ModuleFile,
+    packages: List<Package, MAX_PACKAGES>,
+    package_indices: Map<*const UPackage, usize, PACKAGE_INDICES_CAPACITY>,
+    blueprint_generated_package_file: BufWriter<ModuleFile>,
+    blueprint_generated_counts: Counts,
+    report: Report,
 }
 
 impl Generator {
     pub unsafe fn new() -> Result<Generator, Error> {
-        let mut lib_rs = File::new(sdk_file!("src/lib.rs"))?;
+        let mut lib_rs = ModuleFile::new("lib").context("module lib")?;
         lib_rs.write_str(
             "\
             #![no_std]\n\
@@ -64,70 +76,167 @@ impl Generator {
         Ok(Generator {
             lib_rs,
             packages: List::new(),
-            blueprint_generated_package_file: BufWriter::new(File::new(sdk_file!(
-                "src/blueprint_generated.rs"
-            ))?),
+            package_indices: Map::new(),
+            blueprint_generated_package_file: BufWriter::new(
+                ModuleFile::new("blueprint_generated").context("module blueprint_generated")?,
+            ),
+            blueprint_generated_counts: Counts::default(),
+            report: Report::new(File::new(sdk_file!("generation_report.txt")).context("generation_report.txt")?)?,
         })
     }
 
     pub unsafe fn generate_sdk(&mut self) -> Result<(), Error> {
+        imports::collect().context("import collection")?;
+
+        imports::write_use_block(
+            &mut self.blueprint_generated_package_file,
+            Module::BLUEPRINT_GENERATED,
+        )
+        .context("module blueprint_generated")?;
+
         for object in (*GUObjectArray).iter().filter(|o| !o.is_null()) {
-            if (*object).fast_is(
+            let result = if (*object).fast_is(
                 EClassCastFlags::CASTCLASS_UClass | EClassCastFlags::CASTCLASS_UScriptStruct,
             ) {
-                self.generate_structure(object.cast())?;
+                self.generate_structure(object.cast())
             } else if (*object).fast_is(EClassCastFlags::CASTCLASS_UEnum) {
-                self.generate_enum(object.cast())?;
+                self.generate_enum(object.cast())
+            } else {
+                Ok(())
+            };
+
+            match result {
+                // Nothing was written for the object yet, so it can be left out whole.
+                Err(e @ Error::MaxPackages(_)) if config::SKIP_ON_OVERFLOW => {
+                    self.report.record(Issue::SkippedOnOverflow, &*object, format_args!("{:?}", e))?;
+                    common::context::clear();
+                }
+
+                result => result.context(&*object)?,
             }
         }
+
+        self.write_report_summary()?;
+        self.commit_modules()?;
+
+        Ok(())
+    }
+
+    unsafe fn commit_modules(&mut self) -> Result<(), Error> {
+        self.blueprint_generated_package_file.flush()?;
+
+        let mut num_modules = 2;
+        let mut num_written = u32::from(self.lib_rs.commit().context("module lib")?)
+            + u32::from(
+                self.blueprint_generated_package_file
+                    .get_mut()
+                    .commit()
+                    .context("module blueprint_generated")?,
+            );
+
+        for package in self.packages.iter_mut() {
+            num_modules += 1;
+            num_written += u32::from(package.file.commit().context(&*package.ptr.cast::<UObject>())?);
+        }
+
+        let num_deleted = self.delete_stale_modules()?;
+
+        common::log!(
+            "sdk modules: {} rewritten, {} unchanged, {} stale deleted",
+            num_written,
+            num_modules - num_written,
+            num_deleted
+        );
+
         Ok(())
     }
 
-    unsafe fn get_package(&mut self, object: *mut UObject) -> Result<&mut Package, Error> {
-        let package = (*object).package_mut();
-        let is_unseen_package = (*package).PIEInstanceID == -1;
+    // Deletes the module files left behind by packages that no longer exist.
+    unsafe fn delete_stale_modules(&self) -> Result<u32, Error> {
+        let mut num_deleted = 0;
+        let mut files = FindFiles::new(concat!(sdk_path!(), "/src/*.rs\0"));
+
+        while let Some(file_name) = files.next() {
+            // We only ever generate ASCII file names.
+            let file_name = match str::from_utf8(file_name) {
+                Ok(file_name) => file_name,
+                Err(_) => continue,
+            };
+
+            let module = file_name.strip_suffix(".rs").unwrap_or(file_name);
+
+            let is_generated = module == "lib"
+                || module == "blueprint_generated"
+                || self.packages.iter().any(|p| (*p.ptr).short_name() == module);
 
-        if is_unseen_package {
-            self.register_package(package)?;
+            if !is_generated {
+                let mut path = List::<u8, MAX_PATH>::new();
+                write!(path, concat!(sdk_path!(), "/src/{}\0"), file_name)?;
+                File::delete(path)?;
+                num_deleted += 1;
+            }
         }
 
-        let package = (*package).PIEInstanceID as usize;
-        Ok(self.packages.get_unchecked_mut(package))
+        Ok(num_deleted)
     }
 
-    unsafe fn get_package_file(
-        &mut self,
-        object: *mut UObject,
-    ) -> Result<BufWriter<&mut File>, Error> {
-        Ok(BufWriter::new(&mut self.get_package(object)?.file))
+    unsafe fn write_report_summary(&mut self) -> Result<(), Error> {
+        self.report.begin_packages()?;
+
+        for package in self.packages.iter() {
+            self.report.package((*package.ptr).short_name(), &package.counts)?;
+        }
+
+        self.report.package("blueprint_generated", &self.blueprint_generated_counts)?;
+        self.report.totals()?;
+
+        Ok(())
+    }
+
+    unsafe fn get_package_index(&mut self, object: *mut UObject) -> Result<usize, Error> {
+        let package = (*object).package();
+
+        if let Some(&index) = self.package_indices.get(&package) {
+            Ok(index)
+        } else if self.packages.len() == self.packages.capacity() {
+            Err(Error::MaxPackages(FullNameOf(object)))
+        } else {
+            self.register_package(package)
+        }
     }
 
-    unsafe fn register_package(&mut self, package: *mut UPackage) -> Result<(), Error> {
+    unsafe fn register_package(&mut self, package: *const UPackage) -> Result<usize, Error> {
         let package_name = (*package).short_name();
 
         // Create a Rust module file for this package.
-        let file = {
-            let mut path = List::<u8, 260>::new();
-            write!(
-                &mut path,
-                concat!(sdk_path!(), "/src/{}.rs\0"),
-                package_name
-            )?;
-            File::new(path)?
-        };
+        let mut file = ModuleFile::new(package_name).context(&*package.cast::<UObject>())?;
+
+        // Import the types this package uses from other packages.
+        imports::write_use_block(BufWriter::new(&mut file), Module::of(package.cast()))
+            .context(&*package.cast::<UObject>())?;
 
         // Declare the module in the SDK lib.rs.
         writeln!(&mut self.lib_rs, "pub mod {};", package_name)?;
 
         // Register this package's index in our package cache.
-        (*package).PIEInstanceID = self.packages.len() as i32;
+        let index = self.packages.len();
 
-        let p = Package { ptr: package, file };
+        self.package_indices
+            .insert(package, index)
+            .map_err(|_| Error::MaxPackages(FullNameOf(package.cast())))?;
+
+        let p = Package {
+            ptr: package,
+            file,
+            counts: Counts::default(),
+        };
 
         // Save the package to our cache.
-        self.packages.push(p).map_err(|_| Error::MaxPackages)?;
+        self.packages
+            .push(p)
+            .map_err(|_| Error::MaxPackages(FullNameOf(package.cast())))?;
 
-        Ok(())
+        Ok(index)
     }
 
     unsafe fn generate_enum(&mut self, enumeration: *mut UEnum) -> Result<(), Error> {
@@ -151,7 +260,11 @@ impl Generator {
             get_enum_representation(variants)
         };
 
-        let mut file = self.get_package_file(enumeration.cast())?;
+        let package = self.get_package_index(enumeration.cast())?;
+        let package = self.packages.get_unchecked_mut(package);
+        package.counts.enums += 1;
+
+        let mut file = BufWriter::new(&mut package.file);
 
         writeln!(
             file,
@@ -179,23 +292,31 @@ impl Generator {
             let class = structure.cast::<UClass>();
 
             if (*class).is_blueprint_generated() {
-                return StructGenerator::new(
+                let mut generator = StructGenerator::new(
                     structure,
-                    (*class).package(),
                     &mut self.blueprint_generated_package_file,
                     true,
-                )
-                .generate();
+                    &mut self.report,
+                );
+
+                generator.generate()?;
+                self.blueprint_generated_counts += generator.counts;
+                return Ok(());
             }
         }
 
-        let package = self.get_package(structure.cast())?;
+        let package = self.get_package_index(structure.cast())?;
+        let package = self.packages.get_unchecked_mut(package);
 
         // TODO(perf): Don't need to create a new `BufWriter` if the previous object is from the same package.
         // Reuse previous buffer to reduce total `WriteFile` calls.
         let file = BufWriter::new(&mut package.file);
 
-        StructGenerator::new(structure, package.ptr, file, false).generate()
+        let mut generator = StructGenerator::new(structure, file, false, &mut self.report);
+        generator.generate()?;
+        package.counts += generator.counts;
+
+        Ok(())
     }
 }
 
@@ -249,43 +370,113 @@ unsafe fn write_enum_variant(
     Ok(())
 }
 
-struct StructGenerator<W: Write> {
+struct StructGenerator<'report, W: Write> {
     structure: *mut UStruct,
-    package: *const UPackage,
+    module: Module,
     out: W,
     offset: i32,
-    bitfields: List<List<*const FBoolProperty, 64>, 64>,
+    bitfields: List<List<*const FBoolProperty, MAX_BOOLS_PER_BITFIELD>, MAX_BITFIELDS>,
     last_bitfield_offset: Option<i32>,
     is_blueprint_generated: bool,
-    inherited_type: List<u8, 128>,
+    inherited_type: List<u8, MAX_INHERITED_TYPE_LEN>,
     name: CleanedName,
+    report: &'report mut Report,
+    counts: Counts,
 }
 
-impl<W: Write> StructGenerator<W> {
+impl<'report, W: Write> StructGenerator<'report, W> {
     pub unsafe fn new(
         structure: *mut UStruct,
-        package: *const UPackage,
         out: W,
         is_blueprint_generated: bool,
-    ) -> StructGenerator<W> {
+        report: &'report mut Report,
+    ) -> StructGenerator<'report, W> {
         StructGenerator {
             structure,
-            package,
+            module: Module::of(structure.cast()),
             out,
             offset: 0,
             bitfields: List::new(),
             last_bitfield_offset: None,
             is_blueprint_generated,
             inherited_type: List::new(),
-            name: CleanedName::new((*structure).NamePrivate),
+            name: CleanedName::new((*structure.cast::<UObject>()).NamePrivate),
+            report,
+            counts: Counts::default(),
+        }
+    }
+
+    fn record(
+        &mut self,
+        issue: Issue,
+        object: impl Display,
+        details: impl Display,
+    ) -> Result<(), Error> {
+        self.counts.issues += 1;
+        self.report.record(issue, object, details)?;
+        Ok(())
+    }
+
+    // Leaves `item` of `object` out of the SDK, or fails with `error` if
+    // overflows aren't configured to be skipped.
+    fn skip_on_overflow(
+        &mut self,
+        object: impl Display,
+        item: impl Display,
+        error: Error,
+    ) -> Result<(), Error> {
+        if config::SKIP_ON_OVERFLOW {
+            unsafe {
+                common::context::clear();
+            }
+
+            self.record(
+                Issue::SkippedOnOverflow,
+                object,
+                format_args!("left out {}: {:?}", item, error),
+            )
+        } else {
+            Err(error)
+        }
+    }
+
+    unsafe fn report_if_renamed(&mut self, object: impl Display, name: &CleanedName) -> Result<(), Error> {
+        if name.is_renamed() {
+            self.record(
+                Issue::RenamedIdentifier,
+                object,
+                format_args!("\"{}\" is emitted as {}", name.name.text(), name),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn report_if_unknown(&mut self, object: impl Display, property: *const FProperty) -> Result<(), Error> {
+        if let Some(unknown) = game::find_unknown_property(property) {
+            self.record(
+                Issue::UnknownPropertyType,
+                object,
+                format_args!(
+                    "property \"{}\" has unknown type Id=={}",
+                    (*property).base.NamePrivate,
+                    Hex((*unknown).id().0 as usize)
+                ),
+            )?;
         }
+
+        Ok(())
     }
 
     pub unsafe fn generate(&mut self) -> Result<(), Error> {
         if (*self.structure).PropertiesSize == 0 {
-            return Ok(());
+            return self.record(Issue::SkippedEmptyStruct, &*self.structure, "PropertiesSize is 0");
         }
 
+        let structure = &*self.structure;
+        self.counts.structs += 1;
+        self.report_if_renamed(structure, &CleanedName::new(structure.NamePrivate))?;
+
         self.write_header()?;
         self.add_fields()?;
         writeln!(self.out, "}}\n")?;
@@ -296,6 +487,10 @@ impl<W: Write> StructGenerator<W> {
 
         self.add_deref_impls()?;
 
+        if (*self.structure).fast_is(EClassCastFlags::CASTCLASS_UClass) {
+            self.add_static_class_impl()?;
+        }
+
         self.add_functions()?;
 
         Ok(())
@@ -333,34 +528,15 @@ impl<W: Write> StructGenerator<W> {
             self.name,
         )?;
 
-        let base_name = (*base).name();
-        let base_package = (*base).package();
-
-        let is_base_blueprint_generated = self.is_blueprint_generated
-            && (*base).fast_is(EClassCastFlags::CASTCLASS_UClass)
-            && (*base.cast::<UClass>()).is_blueprint_generated();
-
-        if is_base_blueprint_generated || base_package == self.package {
-            write!(self.inherited_type, "{}", base_name)?;
-            
-            writeln!(
-                self.out,
-                "    // offset: 0, size: {}\n    base: {},\n",
-                Hex(self.offset), base_name
-            )?;
-        } else {
-            let short_name = (*base_package).short_name();
-
-            write!(self.inherited_type, "crate::{}::{}", short_name, base_name)?;
+        let base_name = TypeName::new(base.cast(), self.module);
+        write!(self.inherited_type, "{}", base_name)?;
 
-            writeln!(
-                self.out,
-                "    // offset: 0, size: {}\n    base: crate::{}::{},\n",
-                Hex(self.offset),
-                short_name,
-                base_name
-            )?;
-        }
+        writeln!(
+            self.out,
+            "    // offset: 0, size: {}\n    base: {},\n",
+            Hex(self.offset),
+            base_name
+        )?;
 
         Ok(())
     }
@@ -369,7 +545,8 @@ impl<W: Write> StructGenerator<W> {
         let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
 
         while !property.is_null() {
-            self.process_property(property)?;
+            self.process_property(property)
+                .context(format_args!("property {}", (*property).base.NamePrivate))?;
             property = (*property).base.Next.cast();
         }
 
@@ -389,6 +566,7 @@ impl<W: Write> StructGenerator<W> {
             self.process_bool_property(property.cast())?;
         } else {
             self.add_padding_if_needed(property)?;
+            self.report_if_unknown(&*self.structure, property)?;
 
             if self.is_blueprint_generated {
                 self.process_blueprint_property(property, size)?;
@@ -399,11 +577,7 @@ impl<W: Write> StructGenerator<W> {
                     offset = Hex(self.offset),
                     size = Hex(size),
                     name = (*property).base.NamePrivate,
-                    typ = PropertyDisplayable::new(
-                        property,
-                        self.package,
-                        self.is_blueprint_generated
-                    ),
+                    typ = PropertyDisplayable::new(property, self.module),
                 )?;
             }
 
@@ -419,12 +593,17 @@ impl<W: Write> StructGenerator<W> {
     ) -> Result<(), Error> {
         let offset = (*property).base.Offset;
 
-        if self.last_bitfield_offset.map_or(false, |o| offset == o) {
-            self.bitfields
+        if self.last_bitfield_offset == Some(offset) {
+            let is_full = self
+                .bitfields
                 .last_mut()
                 .ok_or(Error::LastBitfield)?
                 .push(property)
-                .map_err(|_| Error::BitfieldFull)?;
+                .is_err();
+
+            if is_full {
+                self.skip_bitfield_accessors(property, Error::BitfieldFull(FullNameOf(self.structure.cast())))?;
+            }
         } else {
             self.add_padding_if_needed(property.cast())?;
 
@@ -452,13 +631,17 @@ impl<W: Write> StructGenerator<W> {
 
             self.last_bitfield_offset = Some(offset);
 
-            self.bitfields
-                .push({
-                    let mut b = List::new();
-                    b.push(property).map_err(|_| Error::BitfieldFull)?;
-                    b
-                })
-                .map_err(|_| Error::MaxBitfields)?;
+            let mut bitfield = List::new();
+
+            bitfield
+                .push(property)
+                .map_err(|_| Error::BitfieldFull(FullNameOf(self.structure.cast())))?;
+
+            if self.bitfields.push(bitfield).is_err() {
+                // The storage field is already out, so the struct's layout
+                // stays intact.
+                self.skip_bitfield_accessors(property, Error::MaxBitfields(FullNameOf(self.structure.cast())))?;
+            }
 
             self.offset += i32::from(size);
         }
@@ -466,6 +649,18 @@ impl<W: Write> StructGenerator<W> {
         Ok(())
     }
 
+    unsafe fn skip_bitfield_accessors(
+        &mut self,
+        property: *const FBoolProperty,
+        error: Error,
+    ) -> Result<(), Error> {
+        self.skip_on_overflow(
+            &*self.structure,
+            format_args!("the accessors of bitfield \"{}\"", (*property).base.base.NamePrivate),
+            error,
+        )
+    }
+
     unsafe fn process_blueprint_property(
         &mut self,
         property: *const FProperty,
@@ -480,12 +675,13 @@ impl<W: Write> StructGenerator<W> {
 
         let name = (*property).base.NamePrivate;
         let cleaned_name = CleanedName::new(name);
+        self.report_if_renamed(&*self.structure, &cleaned_name)?;
 
         write!(
             self.out,
             "{}: {},",
             cleaned_name,
-            PropertyDisplayable::new(property, self.package, self.is_blueprint_generated)
+            PropertyDisplayable::new(property, self.module)
         )?;
 
         let num_invalid_characters_replaced = cleaned_name.num_invalid_characters_replaced.get();
@@ -540,6 +736,15 @@ impl<W: Write> StructGenerator<W> {
                     "    // WARNING: Property \"{}\" thinks its offset is {}. We think its offset is {}.",
                     (*property).base.NamePrivate, Hex(offset), Hex(self.offset)
                 )?;
+
+                self.record(
+                    Issue::LaggedOffset,
+                    &*self.structure,
+                    format_args!(
+                        "property \"{}\" thinks its offset is {}. We think its offset is {}.",
+                        (*property).base.NamePrivate, Hex(offset), Hex(self.offset)
+                    ),
+                )?;
             }
 
             Ordering::Equal => {
@@ -558,11 +763,22 @@ impl<W: Write> StructGenerator<W> {
             // See comments in `add_padding_if_needed()` for explanation.
             Ordering::Less => self.add_pad_field(self.offset, struct_size)?,
 
-            Ordering::Greater => writeln!(
-                self.out,
-                "    // WARNING: This structure thinks its size is {}. We think its size is {}.",
-                Hex(struct_size), Hex(self.offset)
-            )?,
+            Ordering::Greater => {
+                writeln!(
+                    self.out,
+                    "    // WARNING: This structure thinks its size is {}. We think its size is {}.",
+                    Hex(struct_size), Hex(self.offset)
+                )?;
+
+                self.record(
+                    Issue::SizeMismatch,
+                    &*self.structure,
+                    format_args!(
+                        "structure thinks its size is {}. We think its size is {}.",
+                        Hex(struct_size), Hex(self.offset)
+                    ),
+                )?;
+            }
 
             Ordering::Equal => {}
         }
@@ -606,243 +822,341 @@ impl<W: Write> StructGenerator<W> {
         Ok(())
     }
 
-    unsafe fn add_functions(&mut self) -> Result<(), Error> {
-        let mut property = (*self.structure).Children;
-        let mut has_at_least_one_function = false;
+    unsafe fn add_static_class_impl(&mut self) -> Result<(), Error> {
+        writeln!(
+            self.out,
+            include_str!("static_class.fmt"),
+            name = self.name,
+            full_name = *self.structure,
+        )?;
 
-        while !property.is_null() {
-            if (*property).fast_is(EClassCastFlags::CASTCLASS_UFunction) {
-                if !has_at_least_one_function {
-                    has_at_least_one_function = true;
-                    writeln!(self.out, "impl {} {{", self.name)?;
-                }
+        Ok(())
+    }
 
-                self.process_function(property.cast())?;
-            }
+    unsafe fn add_functions(&mut self) -> Result<(), Error> {
+        let mut field = (*self.structure).Children;
 
-            property = (*property).Next;
-        }
+        while !field.is_null() {
+            if (*field).fast_is(EClassCastFlags::CASTCLASS_UFunction) {
+                self.add_function(field.cast()).context(&*field.cast::<UObject>())?;
+            }
 
-        if has_at_least_one_function {
-            writeln!(self.out, "}}\n")?;
+            field = (*field).Next;
         }
 
         Ok(())
     }
 
-    unsafe fn process_function(&mut self, function: *const UFunction) -> Result<(), Error> {
-        enum Kind {
-            Input,
-            Output,
-        }
+    // Emits the function's parameter struct at module scope, followed by an
+    // `impl` block of its own for the wrapper and the hook helper, so each
+    // function's parameters are only collected once.
+    unsafe fn add_function(&mut self, function: *const UFunction) -> Result<(), Error> {
+        let parameters = match Parameters::new(function, self.module) {
+            Ok(parameters) => parameters,
+            Err(e) => return self.skip_on_overflow(&*self.structure, format_args!("function {}", *function), e),
+        };
+
+        let function = &*function;
+
+        // A copy of `self.name` that reporting a renamed parameter struct can
+        // borrow alongside `self`.
+        let structure_name = CleanedName::new(self.name.name);
+        let function_name = CleanedName::new(function.NamePrivate);
+
+        let suffix = match claim_parameters_name(&structure_name, &function_name, self.module) {
+            Ok(suffix) => suffix,
+            Err(e) => return self.skip_on_overflow(&*self.structure, format_args!("function {}", *function), e),
+        };
+
+        self.counts.functions += 1;
+        self.report_if_renamed(function, &function_name)?;
 
-        struct Parameter {
-            property: *const FProperty,
-            kind: Kind,
+        for parameter in parameters.parameters.iter() {
+            let property = parameter.property;
+            self.report_if_renamed(function, &CleanedName::new((*property).base.NamePrivate))?;
+            self.report_if_unknown(function, property)?;
         }
 
-        struct Parameters {
-            parameters: List<Parameter, 32>,
-            package: *const UPackage,
-            is_struct_blueprint_generated: bool,
-            num_outputs: u8,
+        let parameters_name = ParametersName(&structure_name, &function_name, suffix);
+
+        if suffix > 0 {
+            self.record(
+                Issue::RenamedIdentifier,
+                function,
+                format_args!(
+                    "parameters \"{}\" are emitted as {}",
+                    ParametersName(&structure_name, &function_name, 0),
+                    parameters_name
+                ),
+            )?;
         }
 
-        impl Parameters {
-            fn new(package: *const UPackage, is_struct_blueprint_generated: bool) -> Parameters {
-                Parameters {
-                    parameters: List::new(),
-                    package,
-                    is_struct_blueprint_generated,
-                    num_outputs: 0,
-                }
-            }
+        writeln!(
+            self.out,
+            include_str!("parameters.fmt"),
+            full_name = function,
+            name = parameters_name,
+            declare_struct_fields = DeclareStructFields(&parameters),
+        )?;
 
-            fn add(&mut self, parameter: Parameter) -> Result<(), Error> {
-                self.parameters
-                    .push(parameter)
-                    .map_err(|_| Error::MaxParameters)?;
-                Ok(())
-            }
+        writeln!(self.out, "impl {} {{", structure_name)?;
+
+        writeln!(
+            self.out,
+            include_str!("function.fmt"),
+            name = function_name,
+            full_name = function,
+            parameters = parameters_name,
+            inputs = Inputs(&parameters),
+            outputs = Outputs(&parameters),
+            init_struct_fields = InitStructFields(&parameters),
+            return_values = ReturnValues(&parameters),
+            flags = function.FunctionFlags,
+        )?;
 
-            fn process(&mut self, property: *const FProperty) -> Result<(), Error> {
-                let flags = unsafe { (*property).PropertyFlags };
+        writeln!(
+            self.out,
+            include_str!("hook.fmt"),
+            name = function_name,
+            full_name = function,
+            structure = structure_name,
+            parameters = parameters_name,
+        )?;
 
-                let kind = if flags.contains(EPropertyFlags::CPF_ReturnParm) || (flags.contains(EPropertyFlags::CPF_OutParm) && !flags.contains(EPropertyFlags::CPF_ConstParm)) {
-                    self.num_outputs += 1;
-                    Kind::Output
-                } else if flags.contains(EPropertyFlags::CPF_Parm) {
-                    Kind::Input
-                } else {
-                    return Ok(());
-                };
+        writeln!(self.out, "}}\n")?;
 
-                self.add(Parameter { property, kind })?;
+        Ok(())
+    }
+}
 
-                Ok(())
-            }
+enum Kind {
+    Input,
+    Output,
+}
+
+struct Parameter {
+    property: *const FProperty,
+    kind: Kind,
+}
+
+struct Parameters {
+    function: *const UFunction,
+    parameters: List<Parameter, MAX_PARAMETERS>,
+    module: Module,
+    num_outputs: u8,
+}
+
+impl Parameters {
+    unsafe fn new(function: *const UFunction, module: Module) -> Result<Parameters, Error> {
+        let mut parameters = Parameters {
+            function,
+            parameters: List::new(),
+            module,
+            num_outputs: 0,
+        };
+
+        let mut property = (&*function).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            parameters.process(property)?;
+            property = (*property).base.Next.cast::<FProperty>();
         }
 
-        struct Inputs<'a>(&'a Parameters);
-
-        impl<'a> Display for Inputs<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                for parameter in self.0.parameters.iter() {
-                    if let Kind::Input = parameter.kind {
-                        let parameter = parameter.property;
-                        let name = CleanedName::new(unsafe { (*parameter).base.NamePrivate });
-                        let typ = PropertyDisplayable::new(
-                            parameter,
-                            self.0.package,
-                            self.0.is_struct_blueprint_generated,
-                        );
-                        write!(f, "{}: {}, ", name, typ)?;
-                    }
-                }
+        Ok(parameters)
+    }
 
-                Ok(())
+    fn add(&mut self, parameter: Parameter) -> Result<(), Error> {
+        self.parameters
+            .push(parameter)
+            .map_err(|_| Error::MaxParameters(FullNameOf(self.function.cast())))?;
+        Ok(())
+    }
+
+    fn process(&mut self, property: *const FProperty) -> Result<(), Error> {
+        let flags = unsafe { (*property).PropertyFlags };
+
+        let kind = match flags.parameter_direction() {
+            Some(ParameterDirection::In) => Kind::Input,
+
+            Some(ParameterDirection::Out | ParameterDirection::Ref | ParameterDirection::Return) => {
+                self.num_outputs += 1;
+                Kind::Output
             }
+
+            None => return Ok(()),
+        };
+
+        self.add(Parameter { property, kind })?;
+
+        Ok(())
+    }
+}
+
+// The public name of a function's parameter struct, e.g. `Item_GetItemName_Params`,
+// followed by its number if another function in the module took that name first.
+struct ParametersName<'a>(&'a CleanedName, &'a CleanedName, u32);
+
+impl<'a> Display for ParametersName<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}_{}_Params", self.0, self.1)?;
+
+        if self.2 > 0 {
+            write!(f, "{}", self.2)?;
         }
 
-        struct Outputs<'a>(&'a Parameters);
+        Ok(())
+    }
+}
 
-        impl<'a> Display for Outputs<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                match self.0.num_outputs {
-                    0 => return Ok(()),
-                    1 => write!(f, "-> ")?,
-                    _ => write!(f, "-> (")?,
-                }
+// Returns the number of the first parameter struct name for `function` that
+// no other function in `module` has taken.
+unsafe fn claim_parameters_name(
+    structure: &CleanedName,
+    function: &CleanedName,
+    module: Module,
+) -> Result<u32, Error> {
+    let names = &mut *ptr::addr_of_mut!(PARAMETERS_NAMES);
+    let mut suffix = 0;
+
+    loop {
+        let mut hasher = HashWriter(FnvHasher::new());
+        write!(hasher, "{}", ParametersName(structure, function, suffix))?;
+
+        match names.insert((module, hasher.0.finish()), ()) {
+            Ok(None) => return Ok(suffix),
+            Ok(Some(())) => suffix += 1,
+            Err(_) => return Err(Error::MaxParametersNames),
+        }
+    }
+}
 
-                for parameter in self.0.parameters.iter() {
-                    if let Kind::Output = parameter.kind {
-                        let typ = PropertyDisplayable::new(
-                            parameter.property,
-                            self.0.package,
-                            self.0.is_struct_blueprint_generated,
-                        );
-
-                        if self.0.num_outputs == 1 {
-                            write!(f, "{} ", typ)?;
-                            return Ok(());
-                        } else {
-                            write!(f, "{}, ", typ)?;
-                        }
-                    }
-                }
+struct HashWriter(FnvHasher);
 
-                if self.0.num_outputs > 1 {
-                    write!(f, ") ")?;
-                }
+impl Write for HashWriter {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        self.0.write(s.as_bytes());
+        Ok(())
+    }
+}
 
-                Ok(())
+struct Inputs<'a>(&'a Parameters);
+
+impl<'a> Display for Inputs<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for parameter in self.0.parameters.iter() {
+            if let Kind::Input = parameter.kind {
+                let parameter = parameter.property;
+                let name = CleanedName::new(unsafe { (*parameter).base.NamePrivate });
+                let typ = PropertyDisplayable::new(parameter, self.0.module);
+                write!(f, "{}: {}, ", name, typ)?;
             }
         }
 
-        struct DeclareStructFields<'a>(&'a Parameters);
-
-        impl<'a> Display for DeclareStructFields<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                for parameter in self.0.parameters.iter() {
-                    let property = parameter.property;
-                    let name = CleanedName::new(unsafe { (*property).base.NamePrivate });
-                    let typ = PropertyDisplayable::new(
-                        property,
-                        self.0.package,
-                        self.0.is_struct_blueprint_generated,
-                    );
-
-                    if let Kind::Input = parameter.kind {
-                        write!(f, "\n            {}: {}, ", name, typ)?;
-                    } else {
-                        write!(
-                            f,
-                            "\n            {}: core::mem::MaybeUninit<{}>, ",
-                            name, typ
-                        )?;
-                    }
-                }
+        Ok(())
+    }
+}
 
-                Ok(())
+struct Outputs<'a>(&'a Parameters);
+
+impl<'a> Display for Outputs<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self.0.num_outputs {
+            0 => return Ok(()),
+            1 => write!(f, "-> ")?,
+            _ => write!(f, "-> (")?,
+        }
+
+        for parameter in self.0.parameters.iter() {
+            if let Kind::Output = parameter.kind {
+                let typ = PropertyDisplayable::new(parameter.property, self.0.module);
+
+                if self.0.num_outputs == 1 {
+                    write!(f, "{} ", typ)?;
+                    return Ok(());
+                } else {
+                    write!(f, "{}, ", typ)?;
+                }
             }
         }
 
-        struct InitStructFields<'a>(&'a Parameters);
+        if self.0.num_outputs > 1 {
+            write!(f, ") ")?;
+        }
 
-        impl<'a> Display for InitStructFields<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                for parameter in self.0.parameters.iter() {
-                    let name = CleanedName::new(unsafe { (*parameter.property).base.NamePrivate });
+        Ok(())
+    }
+}
 
-                    if let Kind::Input = parameter.kind {
-                        write!(f, "\n            {}, ", name)?;
-                    } else {
-                        write!(
-                            f,
-                            "\n            {}: core::mem::MaybeUninit::uninit(), ",
-                            name
-                        )?;
-                    }
-                }
+struct DeclareStructFields<'a>(&'a Parameters);
 
-                Ok(())
+impl<'a> Display for DeclareStructFields<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for parameter in self.0.parameters.iter() {
+            let property = parameter.property;
+            let name = CleanedName::new(unsafe { (*property).base.NamePrivate });
+            let typ = PropertyDisplayable::new(property, self.0.module);
+
+            if let Kind::Input = parameter.kind {
+                write!(f, "\n    pub {}: {},", name, typ)?;
+            } else {
+                // Outputs are written by the callee, so they start out uninitialized.
+                write!(f, "\n    pub {}: core::mem::MaybeUninit<{}>,", name, typ)?;
             }
         }
 
-        struct ReturnValues<'a>(&'a Parameters);
-
-        impl<'a> Display for ReturnValues<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                match self.0.num_outputs {
-                    0 => return Ok(()),
-                    1 => write!(f, "\n        ")?,
-                    _ => write!(f, "\n        (")?,
-                }
+        Ok(())
+    }
+}
 
-                for parameter in self.0.parameters.iter() {
-                    if let Kind::Output = parameter.kind {
-                        let name =
-                            CleanedName::new(unsafe { (*parameter.property).base.NamePrivate });
-
-                        if self.0.num_outputs == 1 {
-                            write!(f, "parameters.{}.assume_init()", name)?;
-                            return Ok(());
-                        } else {
-                            write!(f, "parameters.{}.assume_init(), ", name)?;
-                        }
-                    }
-                }
+struct InitStructFields<'a>(&'a Parameters);
 
-                if self.0.num_outputs > 1 {
-                    write!(f, ")")?;
-                }
+impl<'a> Display for InitStructFields<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for parameter in self.0.parameters.iter() {
+            let name = CleanedName::new(unsafe { (*parameter.property).base.NamePrivate });
 
-                Ok(())
+            if let Kind::Input = parameter.kind {
+                write!(f, "\n            {}, ", name)?;
+            } else {
+                write!(
+                    f,
+                    "\n            {}: core::mem::MaybeUninit::uninit(), ",
+                    name
+                )?;
             }
         }
 
-        let mut parameters = Parameters::new(self.package, self.is_blueprint_generated);
-        let mut property = (*function).ChildProperties.cast::<FProperty>();
+        Ok(())
+    }
+}
 
-        while !property.is_null() {
-            parameters.process(property)?;
-            property = (*property).base.Next.cast::<FProperty>();
+struct ReturnValues<'a>(&'a Parameters);
+
+impl<'a> Display for ReturnValues<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self.0.num_outputs {
+            0 => return Ok(()),
+            1 => write!(f, "\n        ")?,
+            _ => write!(f, "\n        (")?,
         }
 
-        let cleaned_name = CleanedName::new((*function).NamePrivate);
+        for parameter in self.0.parameters.iter() {
+            if let Kind::Output = parameter.kind {
+                let name =
+                    CleanedName::new(unsafe { (*parameter.property).base.NamePrivate });
 
-        writeln!(
-            self.out,
-            include_str!("function.fmt"),
-            name = cleaned_name,
-            full_name = *function,
-            inputs = Inputs(&parameters),
-            outputs = Outputs(&parameters),
-            declare_struct_fields = DeclareStructFields(&parameters),
-            init_struct_fields = InitStructFields(&parameters),
-            return_values = ReturnValues(&parameters),
-            flags = (*function).FunctionFlags,
-        )?;
+                if self.0.num_outputs == 1 {
+                    write!(f, "parameters.{}.assume_init()", name)?;
+                    return Ok(());
+                } else {
+                    write!(f, "parameters.{}.assume_init(), ", name)?;
+                }
+            }
+        }
+
+        if self.0.num_outputs > 1 {
+            write!(f, ")")?;
+        }
 
         Ok(())
     }
@@ -860,18 +1174,29 @@ impl CleanedName {
             num_invalid_characters_replaced: Cell::new(0),
         }
     }
+
+    // Whether displaying this name yields something other than the original
+    // name, either through the `Func_` prefix or dropped invalid characters.
+    unsafe fn is_renamed(&self) -> bool {
+        let text = self.name.text();
+        text.starts_with(|c: char| c.is_ascii_digit()) || text.bytes().any(is_invalid)
+    }
+}
+
+fn is_invalid(c: u8) -> bool {
+    !c.is_ascii_alphanumeric() && c != b'_'
 }
 
 impl Display for CleanedName {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-        let mut num_pieces_added = 0;
+        let mut num_pieces_added: u8 = 0;
         let text = unsafe { self.name.text() };
 
         if text.starts_with(|c: char| c.is_ascii_digit()) {
             f.write_str("Func_")?;
         }
 
-        for piece in SplitIterator::new(text.as_bytes(), |c| !c.is_ascii_alphanumeric() && c != b'_') {
+        for piece in SplitIterator::new(text.as_bytes(), is_invalid) {
             if num_pieces_added > 0 {
                 f.write_char('_')?;
             }
@@ -887,8 +1212,9 @@ impl Display for CleanedName {
             write!(f, "_{}", number - 1)?;
         }
 
+        // An empty name has no pieces.
         self.num_invalid_characters_replaced
-            .set(num_pieces_added - 1);
+            .set(num_pieces_added.saturating_sub(1));
 
         if self.num_invalid_characters_replaced.get() > 0 {
             write!(f, "_replaced")?;
@@ -0,0 +1,339 @@
+use crate::config::{
+    IMPORTS_CAPACITY, MAX_MODULE_IMPORTS, MAX_PACKAGES, MAX_PARAMETERS, PACKAGE_INDICES_CAPACITY,
+    TYPE_NAMES_CAPACITY,
+};
+use crate::game;
+
+use common::{
+    Context, EClassCastFlags, FBoolProperty, FProperty, FullNameOf, GUObjectArray, List, Map, UClass,
+    UEnum, UFunction, UObject, UPackage, UStruct,
+};
+
+use core::cmp::Ordering;
+use core::fmt::{self, Display, Formatter, Write};
+use core::ptr;
+
+#[derive(macros::NoPanicErrorDebug)]
+pub enum Error {
+    Fmt(#[from] fmt::Error),
+    MaxImports(FullNameOf),
+    MaxTypeNames(FullNameOf),
+    MaxModuleImports(Module),
+}
+
+// Every generated module's file needs its `use` block before any of the types
+// that use it, but the generator streams types to files in object order. So
+// `collect()` walks the objects once up front, mirroring what the generator
+// will emit, and records each type a module names from another module. That
+// includes leaving out what the generator will skip on overflow, so no module
+// imports from a module that was never written, or imports a type that only a
+// left-out function uses.
+
+// The types each module imports from other modules.
+static mut IMPORTS: Map<(Module, *const UObject), (), IMPORTS_CAPACITY> = Map::new();
+
+// How many structs, classes and enums share each type name. Names come from
+// the name pool, so equal names share the same text pointer.
+static mut TYPE_NAMES: Map<*const u8, u32, TYPE_NAMES_CAPACITY> = Map::new();
+
+// The packages that get a module, claimed in the order the generator will
+// register them until `MAX_PACKAGES` runs out.
+static mut MODULES: Map<*const UPackage, (), PACKAGE_INDICES_CAPACITY> = Map::new();
+
+// The imports of the module whose `use` block is being written.
+static mut MODULE_IMPORTS: List<*const UObject, MAX_MODULE_IMPORTS> = List::new();
+
+// The generated module a type is declared in: the module of its package, or
+// `blueprint_generated` for blueprint-generated classes.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Module(*const UPackage);
+
+impl Module {
+    pub const BLUEPRINT_GENERATED: Module = Module(ptr::null());
+
+    pub unsafe fn of(object: *const UObject) -> Module {
+        let is_blueprint_generated_class = (*object).fast_is(EClassCastFlags::CASTCLASS_UClass)
+            && (*object.cast::<UClass>()).is_blueprint_generated();
+
+        if is_blueprint_generated_class {
+            Module::BLUEPRINT_GENERATED
+        } else {
+            Module((*object).package())
+        }
+    }
+
+    // Whether the generator writes this module, or skips its package because
+    // it ran out of modules.
+    unsafe fn is_generated(&self) -> bool {
+        *self == Module::BLUEPRINT_GENERATED || (*ptr::addr_of!(MODULES)).contains_key(&self.0)
+    }
+
+    // Mirrors the generator registering the module for one of its types.
+    unsafe fn claim(&self) -> bool {
+        let modules = &mut *ptr::addr_of_mut!(MODULES);
+
+        if self.is_generated() {
+            true
+        } else if modules.len() < MAX_PACKAGES {
+            modules.insert(self.0, ()).is_ok()
+        } else {
+            false
+        }
+    }
+
+    unsafe fn name(&self) -> &str {
+        if *self == Module::BLUEPRINT_GENERATED {
+            "blueprint_generated"
+        } else {
+            (*self.0).short_name()
+        }
+    }
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        unsafe { self.name().fmt(f) }
+    }
+}
+
+// How `module` refers to the struct, class or enum `object`. Types from other
+// modules are imported under their own name unless another type shares it, in
+// which case they're imported as `{Module}_{Name}`.
+pub struct TypeName {
+    object: *const UObject,
+    module: Module,
+}
+
+impl TypeName {
+    pub fn new(object: *const UObject, module: Module) -> TypeName {
+        TypeName { object, module }
+    }
+}
+
+impl Display for TypeName {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        unsafe {
+            let name = (*self.object).name();
+            let module = Module::of(self.object);
+
+            if module == self.module || !is_ambiguous(name) {
+                name.fmt(f)
+            } else {
+                write!(f, "{}_{}", module, name)
+            }
+        }
+    }
+}
+
+unsafe fn is_ambiguous(name: &str) -> bool {
+    let type_names = &*ptr::addr_of!(TYPE_NAMES);
+    type_names.get(&name.as_ptr()).is_some_and(|&count| count > 1)
+}
+
+unsafe fn add_type_name(object: *const UObject) -> Result<(), Error> {
+    let type_names = &mut *ptr::addr_of_mut!(TYPE_NAMES);
+    let name = (*object).name().as_ptr();
+
+    if let Some(count) = type_names.get_mut(&name) {
+        *count += 1;
+    } else {
+        type_names
+            .insert(name, 1)
+            .map_err(|_| Error::MaxTypeNames(FullNameOf(object)))?;
+    }
+
+    Ok(())
+}
+
+// Records that `structure`, declared in `module`, names `object`.
+unsafe fn add_import(
+    module: Module,
+    structure: *const UStruct,
+    object: *const UObject,
+) -> Result<(), Error> {
+    if Module::of(object) != module {
+        (*ptr::addr_of_mut!(IMPORTS))
+            .insert((module, object), ())
+            .map_err(|_| Error::MaxImports(FullNameOf(structure.cast())))?;
+    }
+
+    Ok(())
+}
+
+unsafe fn add_property_import(
+    module: Module,
+    structure: *const UStruct,
+    property: *const FProperty,
+) -> Result<(), Error> {
+    if let Some(object) = game::referenced_type(property) {
+        add_import(module, structure, object)?;
+    }
+
+    Ok(())
+}
+
+pub unsafe fn collect() -> Result<(), Error> {
+    for object in (*GUObjectArray).iter().filter(|o| !o.is_null()) {
+        if (*object).fast_is(
+            EClassCastFlags::CASTCLASS_UClass | EClassCastFlags::CASTCLASS_UScriptStruct,
+        ) {
+            add_type_name(object)?;
+
+            if Module::of(object).claim() {
+                collect_structure(object.cast()).context(&*object)?;
+            }
+        } else if (*object).fast_is(EClassCastFlags::CASTCLASS_UEnum) {
+            add_type_name(object)?;
+
+            // The generator leaves out empty enums before registering their
+            // package.
+            let enumeration = &*object.cast::<UEnum>();
+
+            if !enumeration.Names.is_empty() {
+                Module::of(object).claim();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+unsafe fn collect_structure(structure: *const UStruct) -> Result<(), Error> {
+    // The generator skips these, so nothing of theirs ends up in the module.
+    if (*structure).PropertiesSize == 0 {
+        return Ok(());
+    }
+
+    let module = Module::of(structure.cast());
+    let base = (*structure).SuperStruct;
+
+    if !base.is_null() {
+        add_import(module, structure, base.cast())?;
+    }
+
+    let mut property = (*structure).ChildProperties.cast::<FProperty>();
+
+    while !property.is_null() {
+        let is_bitfield = (*property).is(EClassCastFlags::CASTCLASS_FBoolProperty)
+            && (*property.cast::<FBoolProperty>()).is_bitfield();
+
+        if !is_bitfield {
+            add_property_import(module, structure, property)?;
+        }
+
+        property = (*property).base.Next.cast();
+    }
+
+    let mut child = (*structure).Children;
+
+    while !child.is_null() {
+        // The generator leaves out functions with too many parameters.
+        let is_generated_function = (*child).fast_is(EClassCastFlags::CASTCLASS_UFunction)
+            && (&*child.cast::<UFunction>()).parameters().count() <= MAX_PARAMETERS;
+
+        if is_generated_function {
+            let function = &*child.cast::<UFunction>();
+            let mut parameter = function.ChildProperties.cast::<FProperty>();
+
+            while !parameter.is_null() {
+                if (*parameter).PropertyFlags.is_parameter() {
+                    add_property_import(module, structure, parameter)?;
+                }
+
+                parameter = (*parameter).base.Next.cast();
+            }
+        }
+
+        child = (*child).Next;
+    }
+
+    Ok(())
+}
+
+unsafe fn compare(a: *const UObject, b: *const UObject) -> Ordering {
+    Module::of(a)
+        .name()
+        .cmp(Module::of(b).name())
+        .then_with(|| (*a).name().cmp((*b).name()))
+}
+
+// Writes one `use` declaration per module that `module` imports from, sorted
+// by module and then by type name.
+pub unsafe fn write_use_block(mut out: impl Write, module: Module) -> Result<(), Error> {
+    let imports = &mut *ptr::addr_of_mut!(MODULE_IMPORTS);
+    imports.clear();
+
+    for (&(importer, object), _) in (*ptr::addr_of!(IMPORTS)).iter() {
+        if importer == module && Module::of(object).is_generated() {
+            imports
+                .push(object)
+                .map_err(|_| Error::MaxModuleImports(module))?;
+        }
+    }
+
+    // Insertion sort, since the core sorts can panic.
+    for i in 1..imports.len() {
+        let mut j = i;
+
+        while j > 0 {
+            let a: *mut _ = imports.get_unchecked_mut(j - 1);
+            let b: *mut _ = imports.get_unchecked_mut(j);
+
+            if compare(*a, *b) != Ordering::Greater {
+                break;
+            }
+
+            ptr::swap(a, b);
+            j -= 1;
+        }
+    }
+
+    // Different objects can share a name within a module, but each name can
+    // only be imported once.
+    let mut names = imports.iter().copied().peekable();
+
+    while let Some(object) = names.next() {
+        let source = Module::of(object);
+        write!(out, "use crate::{}::", source)?;
+
+        let mut group = 0;
+        let mut next = Some(object);
+
+        while let Some(object) = next {
+            while names.peek().is_some_and(|&o| compare(o, object) == Ordering::Equal) {
+                names.next();
+            }
+
+            let is_last = names.peek().is_none_or(|&o| Module::of(o) != source);
+
+            if group == 0 && !is_last {
+                out.write_char('{')?;
+            } else if group > 0 {
+                out.write_str(", ")?;
+            }
+
+            let name = (*object).name();
+
+            if is_ambiguous(name) {
+                write!(out, "{} as {}", name, TypeName::new(object, module))?;
+            } else {
+                out.write_str(name)?;
+            }
+
+            group += 1;
+            next = if is_last { None } else { names.next() };
+        }
+
+        if group > 1 {
+            out.write_char('}')?;
+        }
+
+        writeln!(out, ";")?;
+    }
+
+    if !imports.is_empty() {
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
@@ -10,16 +10,20 @@ extern "C" {}
 #[link(name = "vcruntime")]
 extern "C" {}
 
-use common::{list, timer, win, GUObjectArray, Hex, NamePoolData, Timer};
+use common::{list, timer, win, Context, GUObjectArray, Hex, NamePoolData, Timer};
 use core::ffi::c_void;
 use core::fmt::{self, Write};
 use core::str;
 
 mod buf_writer;
 use buf_writer::BufWriter;
+mod config;
 mod game;
 mod generator;
 use generator::Generator;
+mod imports;
+mod module_file;
+mod report;
 mod util;
 
 #[derive(macros::NoPanicErrorDebug)]
@@ -44,7 +48,8 @@ unsafe extern "system" fn on_attach(dll: *mut c_void) -> u32 {
     timer::initialize_ticks_per_second();
 
     if let Err(e) = run() {
-        common::log!("error: {:?}", e);
+        common::log!("error: {:?}{}", e, common::context::Chain);
+        common::context::clear();
         common::idle();
     }
 
@@ -76,7 +81,7 @@ unsafe fn dump_globals() -> Result<(), Error> {
 }
 
 unsafe fn dump_names() -> Result<(), Error> {
-    let mut file = BufWriter::new(win::File::new(sdk_file!("global_names.txt"))?);
+    let mut file = BufWriter::new(win::File::new(sdk_file!("global_names.txt")).context("global_names.txt")?);
 
     for (index, name) in (*NamePoolData).iter() {
         let text = (*name).text();
@@ -87,15 +92,16 @@ unsafe fn dump_names() -> Result<(), Error> {
 }
 
 unsafe fn dump_objects() -> Result<(), Error> {
-    let mut file = BufWriter::new(win::File::new(sdk_file!("global_objects.txt"))?);
+    let mut file = BufWriter::new(win::File::new(sdk_file!("global_objects.txt")).context("global_objects.txt")?);
 
     for object in (*GUObjectArray).iter().filter(|o| !o.is_null()) {
         writeln!(
             &mut file,
-            "[{}] {} {}",
+            "[{}] {} {} ({})",
             (*object).InternalIndex,
             *object,
-            Hex(object)
+            Hex(object),
+            (*object).ObjectFlags
         )?;
     }
 
@@ -0,0 +1,117 @@
+use crate::config::MAX_PATH;
+use crate::sdk_path;
+
+use common::win::file::{self, File};
+use common::{FnvHasher, List};
+
+use core::fmt::{self, Write};
+use core::hash::Hasher;
+
+#[derive(macros::NoPanicErrorDebug)]
+pub enum Error {
+    File(#[from] file::Error),
+    Fmt(#[from] fmt::Error),
+    AlreadyCommitted,
+}
+
+// A source file of the generated `sdk` crate.
+//
+// Rewriting every file on every run bumps every modification time, which makes
+// Cargo rebuild the whole `sdk` crate even when nothing changed. So the module
+// is rendered to `{name}.rs.tmp` first, and `commit()` only moves it over
+// `{name}.rs` if the contents differ. A module that's dropped without being
+// committed deletes its temporary file.
+pub struct ModuleFile {
+    path: List<u8, MAX_PATH>,
+    temp_path: List<u8, MAX_PATH>,
+    file: Option<File>,
+    hash: FnvHasher,
+    len: usize,
+    is_committed: bool,
+}
+
+impl ModuleFile {
+    pub unsafe fn new(name: &str) -> Result<ModuleFile, Error> {
+        let mut path = List::new();
+        write!(path, concat!(sdk_path!(), "/src/{}.rs\0"), name)?;
+
+        let mut temp_path = List::new();
+        write!(temp_path, concat!(sdk_path!(), "/src/{}.rs.tmp\0"), name)?;
+
+        Ok(ModuleFile {
+            file: Some(File::new(&temp_path)?),
+            path,
+            temp_path,
+            hash: FnvHasher::new(),
+            len: 0,
+            is_committed: false,
+        })
+    }
+
+    // Returns whether the module on disk was replaced.
+    pub unsafe fn commit(&mut self) -> Result<bool, Error> {
+        // Close the temporary file so it can be moved or deleted.
+        drop(self.file.take().ok_or(Error::AlreadyCommitted)?);
+
+        let is_replaced = if self.is_unchanged() {
+            File::delete(&self.temp_path)?;
+            false
+        } else {
+            File::replace(&self.temp_path, &self.path)?;
+            true
+        };
+
+        self.is_committed = true;
+        Ok(is_replaced)
+    }
+
+    unsafe fn is_unchanged(&self) -> bool {
+        let mut existing = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(_) => return false,
+        };
+
+        let mut buffer = [0; 8192];
+        let mut hash = FnvHasher::new();
+        let mut len = 0;
+
+        loop {
+            match existing.read(&mut buffer) {
+                Ok(0) => break,
+
+                Ok(n) => {
+                    let bytes = buffer.get(..n).unwrap_or_default();
+                    hash.write(bytes);
+                    len += bytes.len();
+                }
+
+                Err(_) => return false,
+            }
+        }
+
+        len == self.len && hash.finish() == self.hash.finish()
+    }
+}
+
+impl Drop for ModuleFile {
+    fn drop(&mut self) {
+        if !self.is_committed {
+            // Close the temporary file so it can be deleted.
+            drop(self.file.take());
+
+            unsafe {
+                let _ = File::delete(&self.temp_path);
+            }
+        }
+    }
+}
+
+impl Write for ModuleFile {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        let file = self.file.as_mut().ok_or(fmt::Error)?;
+        file.write_str(s)?;
+        self.hash.write(s.as_bytes());
+        self.len += s.len();
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::buf_writer::BufWriter;
+
+use common::win::File;
+
+use core::fmt::{self, Display, Formatter, Write};
+use core::ops::AddAssign;
+
+#[derive(Copy, Clone)]
+pub enum Issue {
+    UnknownPropertyType,
+    LaggedOffset,
+    SizeMismatch,
+    RenamedIdentifier,
+    SkippedEmptyStruct,
+    SkippedOnOverflow,
+}
+
+impl Issue {
+    const ALL: [Issue; 6] = [
+        Issue::UnknownPropertyType,
+        Issue::LaggedOffset,
+        Issue::SizeMismatch,
+        Issue::RenamedIdentifier,
+        Issue::SkippedEmptyStruct,
+        Issue::SkippedOnOverflow,
+    ];
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str(match self {
+            Issue::UnknownPropertyType => "unknown property type",
+            Issue::LaggedOffset => "lagged offset",
+            Issue::SizeMismatch => "size mismatch",
+            Issue::RenamedIdentifier => "renamed identifier",
+            Issue::SkippedEmptyStruct => "skipped empty struct",
+            Issue::SkippedOnOverflow => "skipped on overflow",
+        })
+    }
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct Counts {
+    pub structs: u32,
+    pub enums: u32,
+    pub functions: u32,
+    pub issues: u32,
+}
+
+impl AddAssign for Counts {
+    fn add_assign(&mut self, rhs: Self) {
+        self.structs += rhs.structs;
+        self.enums += rhs.enums;
+        self.functions += rhs.functions;
+        self.issues += rhs.issues;
+    }
+}
+
+impl Display for Counts {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{} structs, {} enums, {} functions, {} issues",
+            self.structs, self.enums, self.functions, self.issues
+        )
+    }
+}
+
+// Issues are streamed to the report as the generator finds them. Per-package
+// counts and the totals follow once generation finishes.
+pub struct Report {
+    file: BufWriter<File>,
+    totals: [u32; Issue::ALL.len()],
+}
+
+impl Report {
+    pub fn new(file: File) -> Result<Report, fmt::Error> {
+        let mut report = Report {
+            file: BufWriter::new(file),
+            totals: [0; Issue::ALL.len()],
+        };
+
+        writeln!(report.file, "== Issues ==")?;
+
+        Ok(report)
+    }
+
+    pub fn record(
+        &mut self,
+        issue: Issue,
+        object: impl Display,
+        details: impl Display,
+    ) -> Result<(), fmt::Error> {
+        if let Some(total) = self.totals.get_mut(issue as usize) {
+            *total += 1;
+        }
+
+        writeln!(self.file, "[{}] {} | {}", issue, object, details)
+    }
+
+    pub fn begin_packages(&mut self) -> Result<(), fmt::Error> {
+        writeln!(self.file, "\n== Packages ==")
+    }
+
+    pub fn package(&mut self, name: &str, counts: &Counts) -> Result<(), fmt::Error> {
+        writeln!(self.file, "{}: {}", name, counts)
+    }
+
+    pub fn totals(&mut self) -> Result<(), fmt::Error> {
+        writeln!(self.file, "\n== Totals ==")?;
+
+        let mut sum = 0;
+
+        for (issue, total) in Issue::ALL.iter().zip(self.totals.iter()) {
+            writeln!(self.file, "{}: {}", issue, total)?;
+            sum += total;
+        }
+
+        writeln!(self.file, "total: {}", sum)
+    }
+}